@@ -1,11 +1,26 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::Discriminator;
+use anchor_lang::system_program::{create_account, transfer, CreateAccount, Transfer as SystemTransfer};
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{self, AssociatedToken},
     metadata::{
-        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
-        Metadata as Metaplex,
+        create_master_edition_v3, create_metadata_accounts_v3,
+        mpl_token_metadata::types::{Collection, CollectionDetails, Creator, DataV2, UseMethod, Uses},
+        verify_sized_collection_item, CreateMasterEditionV3, CreateMetadataAccountsV3,
+        update_metadata_accounts_v2, utilize, Metadata as Metaplex, MetadataAccount,
+        UpdateMetadataAccountsV2, Utilize, VerifySizedCollectionItem,
+    },
+    token::{
+        self, initialize_mint2, mint_to, set_authority,
+        spl_token::instruction::AuthorityType, Burn, FreezeAccount, InitializeMint2, Mint, MintTo,
+        SetAuthority,
+        Token, TokenAccount, Transfer,
+    },
+    token_interface::{
+        self, Mint as InterfaceMint, TokenAccount as InterfaceTokenAccount, TokenInterface,
+        TransferChecked,
     },
-    token::{self, mint_to, Mint, MintTo, Token, TokenAccount, Transfer, Burn, burn},
 };
 
 declare_id!("7fcqEt6ieMEgPNQUbVyxGCpVXFPfRsj7xxHgdwqNB1kh");
@@ -21,37 +36,240 @@ pub mod game {
     /// Mint an NFT to a player
     /// Universal minting function - works for any collectible type
     /// The metadata_uri determines what the NFT looks like and represents
+    /// Passing `uses` makes the NFT consumable: it burns after that many uses
+    /// `freeze_authority` picks the freeze mode, see revokes_freeze_authority
+    /// Passing the claimed treasure_record makes this its premium NFT, counted
+    /// against the TierSupply cap for the treasure's tier
+    #[allow(clippy::too_many_arguments)]
     pub fn mint_nft(
         ctx: Context<MintNFT>,
         metadata_title: String,
         metadata_symbol: String,
         metadata_uri: String,
+        creators: Vec<CreatorArg>,
+        is_mutable: bool,
+        freeze_authority: Option<Pubkey>,
+        uses: Option<u64>,
     ) -> Result<()> {
+        // Validate policy and creators before doing any work so bad input fails cheaply
+        check_nft_policy(&ctx.accounts.nft_policy, &metadata_title, &metadata_symbol)?;
+        let creators = build_creators(&creators, ctx.accounts.payer.key())?;
+        let (seller_fee_basis_points, creators) =
+            royalty_terms(&ctx.accounts.royalty_config, creators, ctx.accounts.payer.key())?;
+        let revoke_authorities = revokes_freeze_authority(freeze_authority, ctx.accounts.payer.key())?;
+
         msg!("Minting NFT");
         msg!("Title: {}", metadata_title);
         msg!("URI: {}", metadata_uri);
 
-        // Mint 1 token to the player's token account
+        // Create metadata account with Metaplex standard
+        let metadata_data = DataV2 {
+            name: metadata_title,
+            symbol: metadata_symbol,
+            uri: metadata_uri,
+            seller_fee_basis_points,
+            creators,
+            collection: None,
+            uses: uses.map(|total| Uses {
+                use_method: UseMethod::Burn,
+                remaining: total,
+                total,
+            }),
+        };
+
+        let nft = ctx.accounts.nft_accounts();
+        let tier = ctx.accounts.claimed_tier()?;
+        nft.mint_with_metadata(metadata_data, is_mutable, revoke_authorities, tier)?;
+
+        Ok(())
+    }
+
+    /// Mint an NFT that belongs to a verified collection
+    /// Same as mint_nft, but the metadata points at the collection mint and
+    /// the payer (as collection update authority) verifies the membership
+    pub fn mint_collectible_in_collection(
+        ctx: Context<MintCollectibleInCollection>,
+        metadata_title: String,
+        metadata_symbol: String,
+        metadata_uri: String,
+        creators: Vec<CreatorArg>,
+        is_mutable: bool,
+        freeze_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        check_nft_policy(&ctx.accounts.nft.nft_policy, &metadata_title, &metadata_symbol)?;
+        let creators = build_creators(&creators, ctx.accounts.nft.payer.key())?;
+        let (seller_fee_basis_points, creators) = royalty_terms(
+            &ctx.accounts.nft.royalty_config,
+            creators,
+            ctx.accounts.nft.payer.key(),
+        )?;
+        let revoke_authorities =
+            revokes_freeze_authority(freeze_authority, ctx.accounts.nft.payer.key())?;
+        let collection_mint = ctx.accounts.collection_mint.key();
+
+        // Enforce the collection's fixed supply before anything is minted
+        let collection_state = &mut ctx.accounts.collection_state;
+        collection_state.record_mint()?;
+        emit!(CollectionMinted {
+            collection_mint,
+            minted: collection_state.minted,
+            max_supply: collection_state.max_supply,
+        });
+
+        msg!("Minting NFT into collection {}", collection_mint);
+        msg!("Title: {}", metadata_title);
+        msg!("URI: {}", metadata_uri);
+
+        // Collection starts unverified - Metaplex only trusts it after verification below
+        let metadata_data = DataV2 {
+            name: metadata_title,
+            symbol: metadata_symbol,
+            uri: metadata_uri,
+            seller_fee_basis_points,
+            creators,
+            collection: Some(Collection {
+                verified: false,
+                key: collection_mint,
+            }),
+            uses: None,
+        };
+
+        let nft = ctx.accounts.nft.nft_accounts();
+        let tier = ctx.accounts.nft.claimed_tier()?;
+        nft.mint_with_metadata(metadata_data, is_mutable, revoke_authorities, tier)?;
+
+        // Verify membership in the sized collection (increments collection size)
+        verify_sized_collection_item(
+            CpiContext::new(
+                ctx.accounts.nft.token_metadata_program.to_account_info(),
+                VerifySizedCollectionItem {
+                    payer: ctx.accounts.nft.payer.to_account_info(),
+                    metadata: ctx.accounts.nft.metadata.to_account_info(),
+                    collection_authority: ctx.accounts.nft.payer.to_account_info(),
+                    collection_mint: ctx.accounts.collection_mint.to_account_info(),
+                    collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+                    collection_master_edition: ctx
+                        .accounts
+                        .collection_master_edition
+                        .to_account_info(),
+                },
+            ),
+            None, // collection_authority_record (payer is the update authority)
+        )?;
+
+        msg!("Collection membership verified");
+
+        Ok(())
+    }
+
+    /// Mint several NFTs to one player in a single transaction
+    /// remaining_accounts holds NFT_BATCH_ACCOUNTS entries per NFT, in order:
+    /// [mint (signer), player token account, metadata, master edition]
+    /// Each NFT costs roughly 100k compute units and a mint signature, so
+    /// MAX_NFT_BATCH_SIZE keeps a batch inside the 1.4M CU / 1232-byte limits
+    /// (request a higher compute unit limit in the transaction for batches > 2)
+    pub fn mint_nft_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintNftBatch<'info>>,
+        nfts: Vec<NftMintArgs>,
+    ) -> Result<()> {
+        require!(!nfts.is_empty(), ErrorCode::AccountCountMismatch);
+        require!(nfts.len() <= MAX_NFT_BATCH_SIZE, ErrorCode::BatchTooLarge);
+        require!(
+            ctx.remaining_accounts.len() == nfts.len() * NFT_BATCH_ACCOUNTS,
+            ErrorCode::AccountCountMismatch
+        );
+
+        msg!("Minting batch of {} NFTs for player {}", nfts.len(), ctx.accounts.player.key());
+
+        for (args, accounts) in nfts
+            .into_iter()
+            .zip(ctx.remaining_accounts.chunks(NFT_BATCH_ACCOUNTS))
+        {
+            check_nft_policy(&ctx.accounts.nft_policy, &args.metadata_title, &args.metadata_symbol)?;
+            let creators = build_creators(&args.creators, ctx.accounts.payer.key())?;
+            let (seller_fee_basis_points, creators) =
+                royalty_terms(&ctx.accounts.royalty_config, creators, ctx.accounts.payer.key())?;
+            let revoke_authorities =
+                revokes_freeze_authority(args.freeze_authority, ctx.accounts.payer.key())?;
+
+            let nft = NftMintAccounts {
+                player: ctx.accounts.player.key(),
+                payer: ctx.accounts.payer.to_account_info(),
+                mint: accounts[0].clone(),
+                token_account: accounts[1].clone(),
+                metadata: accounts[2].clone(),
+                master_edition: accounts[3].clone(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            };
+
+            msg!("Minting NFT {}", nft.mint.key());
+            msg!("Title: {}", args.metadata_title);
+
+            // remaining_accounts are not initialized by Anchor, so create them here
+            ctx.accounts.init_mint_and_token_account(&nft)?;
+
+            let metadata_data = DataV2 {
+                name: args.metadata_title,
+                symbol: args.metadata_symbol,
+                uri: args.metadata_uri,
+                seller_fee_basis_points,
+                creators,
+                collection: None,
+                uses: None,
+            };
+
+            nft.mint_with_metadata(metadata_data, args.is_mutable, revoke_authorities, None)?;
+        }
+
+        msg!("Batch mint complete");
+
+        Ok(())
+    }
+
+    /// Create a collection NFT that collectibles can be grouped under
+    /// Mints the collection token to the payer, creates sized-collection
+    /// metadata and a master edition so items can be verified against it.
+    /// mint_collectible_in_collection stops at max_supply items (0 = unlimited)
+    pub fn create_collection(
+        ctx: Context<CreateCollection>,
+        metadata_title: String,
+        metadata_symbol: String,
+        metadata_uri: String,
+        max_supply: u64,
+    ) -> Result<()> {
+        validate_metadata(&metadata_title, &metadata_symbol, &metadata_uri)?;
+
+        let collection_state = &mut ctx.accounts.collection_state;
+        collection_state.collection_mint = ctx.accounts.collection_mint.key();
+        collection_state.minted = 0;
+        collection_state.max_supply = max_supply;
+        collection_state.bump = ctx.bumps.collection_state;
+
+        msg!("Creating collection");
+        msg!("Title: {}", metadata_title);
+        msg!("URI: {}", metadata_uri);
+
+        // Mint the single collection token to the payer
         mint_to(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 MintTo {
                     authority: ctx.accounts.payer.to_account_info(),
                     to: ctx.accounts.token_account.to_account_info(),
-                    mint: ctx.accounts.mint.to_account_info(),
+                    mint: ctx.accounts.collection_mint.to_account_info(),
                 },
             ),
-            1, // NFTs have a supply of 1
+            1,
         )?;
 
-        msg!("Token minted successfully");
-
-        // Create metadata account with Metaplex standard
         let metadata_data = DataV2 {
             name: metadata_title,
             symbol: metadata_symbol,
             uri: metadata_uri,
-            seller_fee_basis_points: 0, // No royalties
+            seller_fee_basis_points: 0,
             creators: None,
             collection: None,
             uses: None,
@@ -62,7 +280,7 @@ pub mod game {
                 ctx.accounts.token_metadata_program.to_account_info(),
                 CreateMetadataAccountsV3 {
                     payer: ctx.accounts.payer.to_account_info(),
-                    mint: ctx.accounts.mint.to_account_info(),
+                    mint: ctx.accounts.collection_mint.to_account_info(),
                     metadata: ctx.accounts.metadata.to_account_info(),
                     mint_authority: ctx.accounts.payer.to_account_info(),
                     update_authority: ctx.accounts.payer.to_account_info(),
@@ -71,591 +289,7705 @@ pub mod game {
                 },
             ),
             metadata_data,
-            false, // is_mutable
-            true,  // update_authority_is_signer
-            None,  // collection_details
+            true, // is_mutable (collection metadata may be updated later)
+            true, // update_authority_is_signer
+            Some(CollectionDetails::V1 { size: 0 }), // sized collection
         )?;
 
-        msg!("Metadata created successfully");
-
-        Ok(())
-    }
-
-    // ====================================================================
-    // TREASURE HIDING SYSTEM (Optional - for premium NFTs)
-    // ====================================================================
-
-    /// Initialize the treasure vault (one-time setup by admin)
-    /// This creates the vault that can accept hidden treasure
-    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
-        let vault = &mut ctx.accounts.vault;
-        vault.authority = ctx.accounts.authority.key();
-        vault.total_hidden = 0;
-        vault.total_claimed = 0;
-        vault.bump = ctx.bumps.vault;
+        // Master edition is required before items can be verified against the collection
+        create_master_edition_v3(
+            CpiContext::new(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMasterEditionV3 {
+                    edition: ctx.accounts.master_edition.to_account_info(),
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    update_authority: ctx.accounts.payer.to_account_info(),
+                    mint_authority: ctx.accounts.payer.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+            ),
+            Some(0), // max_supply (no prints of the collection NFT)
+        )?;
 
-        msg!("Treasure vault initialized!");
-        msg!("Authority: {}", vault.authority);
+        msg!("Collection created: {}", ctx.accounts.collection_mint.key());
+        msg!("Max supply: {}", max_supply);
 
         Ok(())
     }
 
-    /// Hide treasure (tokens) to earn the right to mint a premium NFT
-    /// Player sends tokens → vault stores them → creates hidden treasure record
-    pub fn hide_treasure(
-        ctx: Context<HideTreasure>,
-        amount: u64,
-        treasure_id: i64,
+    /// Update the title and URI of a mutable NFT (e.g. evolve its art)
+    /// Only the update authority stored on the metadata can call this
+    pub fn update_nft_metadata(
+        ctx: Context<UpdateNftMetadata>,
+        new_uri: String,
+        new_title: String,
     ) -> Result<()> {
-        // Validate minimum treasure amount (100 tokens with 6 decimals = 100,000,000)
-        require!(amount >= 100_000_000, ErrorCode::InsufficientTreasure);
+        let metadata = &ctx.accounts.metadata;
+        let symbol = metadata.symbol.trim_end_matches('\0').to_string();
+        validate_metadata(&new_title, &symbol, &new_uri)?;
 
-        msg!("Player hiding {} tokens as treasure", amount);
+        msg!("Updating NFT metadata for mint {}", metadata.mint);
+        msg!("New title: {}", new_title);
+        msg!("New URI: {}", new_uri);
 
-        // Transfer tokens from player to vault
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.player_token_account.to_account_info(),
-            to: ctx.accounts.vault_token_account.to_account_info(),
-            authority: ctx.accounts.player.to_account_info(),
+        // Keep everything except the title and URI as it is on-chain today
+        let metadata_data = DataV2 {
+            name: new_title,
+            symbol,
+            uri: new_uri,
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            creators: metadata.creators.clone(),
+            collection: metadata.collection.clone(),
+            uses: metadata.uses.clone(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-        token::transfer(cpi_ctx, amount)?;
+        update_metadata_accounts_v2(
+            CpiContext::new(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    update_authority: ctx.accounts.update_authority.to_account_info(),
+                },
+            ),
+            None, // new_update_authority
+            Some(metadata_data),
+            None, // primary_sale_happened
+            None, // is_mutable
+        )?;
 
-        msg!("Treasure hidden successfully");
+        msg!("Metadata updated successfully");
 
-        // Record hidden treasure in player's PDA
-        let treasure_record = &mut ctx.accounts.treasure_record;
-        treasure_record.player = ctx.accounts.player.key();
-        treasure_record.amount = amount;
-        treasure_record.timestamp = treasure_id;
-        treasure_record.claimed = false;
-        treasure_record.bump = ctx.bumps.treasure_record;
+        Ok(())
+    }
 
-        // Calculate tier based on treasure amount
-        treasure_record.tier = calculate_tier(amount);
+    /// Admin function to authorize a backend wallet to mint NFTs
+    /// Creates the minter registry on first use
+    pub fn add_minter(ctx: Context<AddMinter>, minter: Pubkey) -> Result<()> {
+        let registry = &mut ctx.accounts.minter_registry;
+        registry.bump = ctx.bumps.minter_registry;
 
-        // Update vault stats
-        let vault = &mut ctx.accounts.vault;
-        vault.total_hidden = vault.total_hidden.checked_add(amount).unwrap();
+        require!(!registry.minters.contains(&minter), ErrorCode::MinterAlreadyRegistered);
+        require!(registry.minters.len() < MAX_MINTERS, ErrorCode::TooManyMinters);
 
-        msg!("Treasure recorded! Tier: {}", treasure_record.tier);
-        msg!("Player can now claim their premium NFT");
+        registry.minters.push(minter);
+
+        msg!("Minter added: {}", minter);
 
         Ok(())
     }
 
-    /// Claim hidden treasure to receive premium NFT
-    /// This marks the treasure as claimed so player can mint their premium NFT
-    pub fn claim_treasure(ctx: Context<ClaimTreasure>) -> Result<()> {
-        let treasure_record = &mut ctx.accounts.treasure_record;
+    /// Admin function to revoke a backend wallet's minting rights
+    pub fn remove_minter(ctx: Context<RemoveMinter>, minter: Pubkey) -> Result<()> {
+        let registry = &mut ctx.accounts.minter_registry;
+        let index = registry
+            .minters
+            .iter()
+            .position(|m| *m == minter)
+            .ok_or(ErrorCode::UnauthorizedMinter)?;
 
-        // Validate not already claimed
-        require!(!treasure_record.claimed, ErrorCode::AlreadyClaimed);
+        registry.minters.swap_remove(index);
 
-        msg!("Player claiming treasure (tier {})", treasure_record.tier);
+        msg!("Minter removed: {}", minter);
 
-        // Mark as claimed
-        treasure_record.claimed = true;
+        Ok(())
+    }
 
-        // Update vault stats
-        let vault = &mut ctx.accounts.vault;
-        vault.total_claimed = vault.total_claimed.checked_add(1).unwrap();
+    /// Admin function to restrict minted NFTs to an approved symbol and name prefix
+    /// Guards against a backend bug minting off-brand metadata; disable the
+    /// policy to allow open minting. upgrade_monster derives its own symbols
+    /// and is not subject to the policy
+    pub fn set_nft_policy(
+        ctx: Context<SetNftPolicy>,
+        enabled: bool,
+        symbol: String,
+        name_prefix: Option<String>,
+    ) -> Result<()> {
+        require!(symbol.len() <= MAX_SYMBOL_LENGTH, ErrorCode::SymbolTooLong);
+        if let Some(prefix) = &name_prefix {
+            require!(prefix.len() <= MAX_NAME_LENGTH, ErrorCode::NameTooLong);
+        }
 
-        msg!("Treasure claimed! Total claims: {}", vault.total_claimed);
+        let policy = &mut ctx.accounts.nft_policy;
+        policy.enabled = enabled;
+        policy.symbol = symbol;
+        policy.name_prefix = name_prefix;
+        policy.bump = ctx.bumps.nft_policy;
 
-        // Note: Actual NFT minting happens separately via mint_nft instruction
-        // This just validates the player has hidden treasure and tracks the claim
+        msg!("NFT policy enabled: {}", policy.enabled);
+        msg!("Symbol: {}, name prefix: {:?}", policy.symbol, policy.name_prefix);
 
         Ok(())
     }
 
-    // ====================================================================
-    // TREASURE SEARCH SYSTEM
-    // ====================================================================
-
-    /// Search for treasure at specific map coordinates
-    /// Player submits a search transaction with x,y coordinates
-    /// This creates a searchable record that the monitoring service can detect
-    pub fn search_treasure(
-        ctx: Context<SearchTreasure>,
-        x: i32,
-        y: i32,
-        search_id: i64,
+    /// Admin function to set the royalty every minted NFT carries
+    /// Once set, mint_nft, mint_nft_batch, mint_collectible_in_collection and
+    /// mint_collectible_gated take their seller fee from it, and any creators
+    /// the caller supplies must include the recipient
+    pub fn set_royalty_config(
+        ctx: Context<SetRoyaltyConfig>,
+        recipient: Pubkey,
+        seller_fee_basis_points: u16,
     ) -> Result<()> {
-        msg!("Player searching for treasure at coordinates ({}, {})", x, y);
-
-        // Charge 1 BOOTY token for the search (1 token with 6 decimals = 1,000,000)
-        let search_fee = 1_000_000_u64;
-        msg!("Charging {} BOOTY tokens for search", search_fee / 1_000_000);
+        require!(
+            seller_fee_basis_points as u64 <= BPS_DENOMINATOR,
+            ErrorCode::InvalidRoyaltyBps
+        );
 
-        // Transfer BOOTY tokens from player to vault
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.player_booty_account.to_account_info(),
-            to: ctx.accounts.vault_booty_account.to_account_info(),
-            authority: ctx.accounts.player.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let royalty_config = &mut ctx.accounts.royalty_config;
+        royalty_config.recipient = recipient;
+        royalty_config.seller_fee_basis_points = seller_fee_basis_points;
+        royalty_config.bump = ctx.bumps.royalty_config;
 
-        token::transfer(cpi_ctx, search_fee)?;
+        msg!("Royalty: {} bps to {}", seller_fee_basis_points, recipient);
 
-        msg!("Search fee paid successfully");
+        Ok(())
+    }
 
-        // Record the search in player's PDA
-        let search_record = &mut ctx.accounts.search_record;
-        search_record.player = ctx.accounts.player.key();
-        search_record.x = x;
-        search_record.y = y;
-        search_record.timestamp = search_id;
-        search_record.found = false;
-        search_record.bump = ctx.bumps.search_record;
+    /// Admin function to set the Merkle root of the drop allowlist
+    /// Changing the root starts a fresh set of claim bitmaps
+    pub fn set_allowlist_root(ctx: Context<SetAllowlistRoot>, root: [u8; 32]) -> Result<()> {
+        let allowlist = &mut ctx.accounts.allowlist;
+        allowlist.root = root;
+        allowlist.bump = ctx.bumps.allowlist;
 
-        msg!("Search recorded at ({}, {})", x, y);
-        msg!("Search ID: {}", search_id);
+        msg!("Allowlist root updated");
 
         Ok(())
     }
 
-    /// Admin function to whitelist a token mint
-    /// This allows adding new tokens that can be hidden as treasure
-    pub fn whitelist_token(
-        ctx: Context<WhitelistToken>,
-        token_mint: Pubkey,
+    /// Mint a drop NFT to an allowlisted player
+    /// The player proves their wallet is in the allowlist Merkle tree at
+    /// `leaf_index`; each leaf can only be minted once
+    pub fn mint_collectible_gated(
+        ctx: Context<MintCollectibleGated>,
+        proof: Vec<[u8; 32]>,
+        leaf: [u8; 32],
+        leaf_index: u32,
+        metadata_title: String,
+        metadata_symbol: String,
+        metadata_uri: String,
     ) -> Result<()> {
-        let whitelist = &mut ctx.accounts.whitelist;
-        whitelist.token_mint = token_mint;
-        whitelist.enabled = true;
-        whitelist.bump = ctx.bumps.whitelist;
+        check_nft_policy(&ctx.accounts.nft_policy, &metadata_title, &metadata_symbol)?;
+
+        // The leaf must be this player's wallet, so proofs can't be shared
+        let player_leaf = keccak::hash(ctx.accounts.player.key().as_ref()).to_bytes();
+        require!(leaf == player_leaf, ErrorCode::InvalidMerkleProof);
+        require!(
+            verify_merkle_proof(&proof, ctx.accounts.allowlist.root, leaf, leaf_index),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        // Mark the leaf consumed before minting
+        let claims = &mut ctx.accounts.allowlist_claims;
+        claims.bump = ctx.bumps.allowlist_claims;
+        let bit = (leaf_index % ALLOWLIST_LEAVES_PER_BITMAP) as usize;
+        let mask = 1u8 << (bit % 8);
+        require!(claims.bits[bit / 8] & mask == 0, ErrorCode::AllowlistAlreadyClaimed);
+        claims.bits[bit / 8] |= mask;
+
+        msg!("Allowlist mint for player {} (leaf {})", ctx.accounts.player.key(), leaf_index);
+
+        let (seller_fee_basis_points, creators) =
+            royalty_terms(&ctx.accounts.royalty_config, None, ctx.accounts.player.key())?;
+        let metadata_data = DataV2 {
+            name: metadata_title,
+            symbol: metadata_symbol,
+            uri: metadata_uri,
+            seller_fee_basis_points,
+            creators,
+            collection: None,
+            uses: None,
+        };
 
-        msg!("Token whitelisted: {}", token_mint);
+        ctx.accounts
+            .nft_accounts()
+            .mint_with_metadata(metadata_data, false, true, None)?;
 
         Ok(())
     }
 
-    /// Admin function to update vault settings
-    pub fn update_vault(
-        ctx: Context<UpdateVault>,
-        new_authority: Option<Pubkey>,
-    ) -> Result<()> {
-        let vault = &mut ctx.accounts.vault;
+    /// Admin function to cap how many NFTs of each tier can ever be minted
+    /// caps[i] limits tier i + 1 (tiers above the last share its cap); 0 = uncapped.
+    /// A cap below the minted count simply sells that tier out
+    pub fn set_tier_caps(ctx: Context<SetTierCaps>, caps: [u64; NFT_TIER_COUNT]) -> Result<()> {
+        let tier_supply = &mut ctx.accounts.tier_supply;
+        tier_supply.caps = caps;
+        tier_supply.bump = ctx.bumps.tier_supply;
 
-        if let Some(authority) = new_authority {
-            vault.authority = authority;
-            msg!("Vault authority updated to: {}", authority);
+        msg!("Tier caps: {:?} (minted {:?})", caps, tier_supply.minted);
+
+        Ok(())
+    }
+
+    /// Combine two monsters of the same family and tier into one of the next tier
+    /// Monster symbols are the family name followed by a tier digit (e.g. "KRAKEN1");
+    /// both inputs are burned and the upgraded monster is minted to the player,
+    /// counted against the TierSupply cap for its new tier
+    pub fn upgrade_monster(
+        ctx: Context<UpgradeMonster>,
+        metadata_title: String,
+        metadata_uri: String,
+    ) -> Result<()> {
+        let symbol_a = ctx.accounts.input_metadata_a.symbol.trim_matches('\0');
+        let symbol_b = ctx.accounts.input_metadata_b.symbol.trim_matches('\0');
+        require!(symbol_a == symbol_b, ErrorCode::MismatchedInputs);
+
+        let (family, tier) = parse_monster_symbol(symbol_a)?;
+        require!(tier < MAX_MONSTER_TIER, ErrorCode::MaxMonsterTier);
+        let upgraded_symbol = format!("{}{}", family, tier + 1);
+
+        msg!("Upgrading two {} monsters to {}", symbol_a, upgraded_symbol);
+
+        // Burn both inputs from the player's wallet
+        for (mint, token_account) in [
+            (&ctx.accounts.input_mint_a, &ctx.accounts.input_token_account_a),
+            (&ctx.accounts.input_mint_b, &ctx.accounts.input_token_account_b),
+        ] {
+            token::burn(
+                CpiContext::new(
+                    ctx.accounts.nft.token_program.to_account_info(),
+                    token::Burn {
+                        mint: mint.to_account_info(),
+                        from: token_account.to_account_info(),
+                        authority: ctx.accounts.owner.to_account_info(),
+                    },
+                ),
+                1,
+            )?;
         }
 
+        let metadata_data = DataV2 {
+            name: metadata_title,
+            symbol: upgraded_symbol,
+            uri: metadata_uri,
+            seller_fee_basis_points: 0, // No royalties
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+
+        // The upgraded tier comes from the inputs, never from a treasure
+        require!(
+            ctx.accounts.nft.treasure_record.is_none(),
+            ErrorCode::InvalidTreasureRecord
+        );
+        let nft = ctx.accounts.nft.nft_accounts();
+        let tier_supply = ctx
+            .accounts
+            .nft
+            .tier_supply
+            .as_mut()
+            .ok_or(ErrorCode::TierSupplyRequired)?;
+        nft.mint_with_metadata(metadata_data, false, true, Some((tier + 1, tier_supply)))?;
+
+        emit!(UpgradeCompleted {
+            burned: [ctx.accounts.input_mint_a.key(), ctx.accounts.input_mint_b.key()],
+            minted: ctx.accounts.nft.mint.key(),
+        });
+
         Ok(())
     }
 
     // ====================================================================
-    // $BOOTY TOKEN SYSTEM
+    // TREASURE HIDING SYSTEM (Optional - for premium NFTs)
     // ====================================================================
 
-    /// Initialize the $BOOTY token mint (one-time setup)
-    /// Creates a standard SPL token that the game program controls
-    pub fn initialize_booty_mint(
-        ctx: Context<InitializeBootyMint>,
-        decimals: u8,
-        max_supply: Option<u64>,
-    ) -> Result<()> {
-        let booty_state = &mut ctx.accounts.booty_state;
-        booty_state.mint = ctx.accounts.booty_mint.key();
-        booty_state.authority = ctx.accounts.authority.key();
-        booty_state.total_mined = 0;
-        booty_state.total_burned = 0;
-        booty_state.max_supply = max_supply;
-        booty_state.bump = ctx.bumps.booty_state;
+    /// Initialize a treasure vault (one-time setup by admin per vault_id)
+    /// This creates the vault that can accept hidden treasure. Vault 0 is the
+    /// original single vault; further ids host independent games. Only the
+    /// ProgramConfig authority may create one, and the config must already
+    /// exist (from initialize_game or migrate_config)
+    pub fn initialize_vault(ctx: Context<InitializeVault>, vault_id: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.set_inner(TreasureVault::new(
+            ctx.accounts.authority.key(),
+            ctx.bumps.vault,
+            vault_id,
+        ));
 
-        msg!("$BOOTY token initialized!");
-        msg!("Mint: {}", booty_state.mint);
-        msg!("Authority: {}", booty_state.authority);
-        msg!("Decimals: {}", decimals);
-        if let Some(max) = max_supply {
-            msg!("Max supply: {}", max);
-        } else {
-            msg!("Max supply: unlimited");
-        }
+        msg!("Treasure vault {} initialized!", vault_id);
+        msg!("Authority: {}", vault.authority);
 
         Ok(())
     }
 
-    /// Mine (mint) $BOOTY tokens for a player
-    /// Called when a player buries treasure - rewards them with BOOTY based on hidden treasure amount
-    pub fn mine_booty(
-        ctx: Context<MineBooty>,
-        amount: u64,
+    /// Bootstrap a fresh deployment in one transaction: the program config,
+    /// vault 0 and the BOOTY mint with its state. Any failing step reverts the
+    /// whole transaction, so the program is never left half-configured
+    pub fn initialize_game(
+        ctx: Context<InitializeGame>,
+        booty_decimals: u8,
+        booty_max_supply: Option<u64>,
     ) -> Result<()> {
-        // Check max supply if set
-        if let Some(max_supply) = ctx.accounts.booty_state.max_supply {
-            let new_total = ctx.accounts.booty_state.total_mined
-                .checked_add(amount)
-                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(booty_decimals <= MAX_BOOTY_DECIMALS, ErrorCode::InvalidDecimals);
+
+        let authority = ctx.accounts.authority.key();
+        ctx.accounts
+            .config
+            .set_inner(ProgramConfig::new(authority, ctx.bumps.config));
+        ctx.accounts
+            .vault
+            .set_inner(TreasureVault::new(authority, ctx.bumps.vault, 0));
+        ctx.accounts.booty_state.set_inner(BootyState::new(
+            ctx.accounts.booty_mint.key(),
+            authority,
+            ctx.bumps.booty_state,
+            booty_decimals,
+            booty_max_supply,
+        ));
+
+        msg!("Game initialized!");
+        msg!("Authority: {}", authority);
+        msg!("BOOTY mint: {}", ctx.accounts.booty_mint.key());
+
+        Ok(())
+    }
+
+    /// One-time creation of the ProgramConfig PDA for a deployment that
+    /// predates it; the original vault's admin becomes the config authority.
+    /// Fresh deployments get their config from initialize_game
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        // The authority is the first field of every vault layout, so this works
+        // before migrate_account has upgraded the vault
+        let authority = {
+            let data = ctx.accounts.vault.try_borrow_data()?;
             require!(
-                new_total <= max_supply,
-                ErrorCode::MaxSupplyExceeded
+                data.len() >= 8 + 32 && data[..8] == TreasureVault::DISCRIMINATOR,
+                ErrorCode::InvalidMigrationTarget
             );
-        }
+            Pubkey::try_from(&data[8..8 + 32]).map_err(|_| ErrorCode::InvalidMigrationTarget)?
+        };
+        require_keys_eq!(authority, ctx.accounts.authority.key(), ErrorCode::Unauthorized);
 
-        msg!("Mining {} BOOTY tokens for player {}", amount, ctx.accounts.player.key());
+        ctx.accounts
+            .config
+            .set_inner(ProgramConfig::new(authority, ctx.bumps.config));
 
-        // Mint tokens using game program as authority
-        let bump = ctx.accounts.booty_state.bump;
-        let seeds = &[
-            b"booty-state".as_ref(),
-            &[bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        msg!("Program config migrated from vault");
+
+        Ok(())
+    }
+
+    /// Report which one-time setup steps have already run, without side effects
+    /// Deployment scripts simulate this and decode the returned InitializationStatus
+    /// to skip steps that are done; a closed account reads as uninitialized again
+    pub fn is_initialized(ctx: Context<IsInitialized>) -> Result<InitializationStatus> {
+        Ok(InitializationStatus {
+            vault: is_program_account(&ctx.accounts.vault, &TreasureVault::DISCRIMINATOR),
+            booty_state: is_program_account(&ctx.accounts.booty_state, &BootyState::DISCRIMINATOR),
+        })
+    }
+
+    /// Hide treasure (tokens) to earn the right to mint a premium NFT
+    /// Player sends tokens → vault stores them → creates hidden treasure record
+    pub fn hide_treasure(
+        ctx: Context<HideTreasure>,
+        amount: u64,
+        treasure_id: i64,
+        referrer: Option<Pubkey>,
+        expected_tier: Option<u8>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+
+        // A treasure_id is single-use even after its record is closed, so a
+        // reclaimed treasure can't be re-hidden under the same id
+        let nonces = &mut ctx.accounts.claimed_nonces;
+        nonces.player = ctx.accounts.player.key();
+        nonces.shard = ClaimedNonces::shard_of(treasure_id);
+        nonces.bump = ctx.bumps.claimed_nonces;
+        nonces.mark(treasure_id)?;
+
+        let enforce_ids = ctx.accounts.config.require_monotonic_ids;
+        ctx.accounts
+            .player_profile
+            .record_treasure_id(treasure_id, enforce_ids)?;
+
+        // Validate minimum treasure amount
+        let decimals = ctx.accounts.whitelist.decimals;
+        let min_amount = ctx.accounts.whitelist.min_deposit()?;
+        require!(amount >= min_amount, ErrorCode::InsufficientTreasure);
+
+        // Enforce the lifetime per-player deposit cap (0 = unlimited)
+        let total_deposited = ctx
+            .accounts
+            .player_profile
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let max_player_deposit = ctx.accounts.config.max_player_deposit;
+        if max_player_deposit > 0 {
+            require!(
+                total_deposited <= max_player_deposit,
+                ErrorCode::PlayerDepositCapExceeded
+            );
+        }
+
+        msg!("Player hiding {} tokens as treasure", amount);
+
+        // Split off the protocol fee - only the net amount counts as treasure
+        let fee = calculate_fee(amount, ctx.accounts.config.fee_bps)?;
+        let net_amount = amount
+            .checked_sub(fee)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        if fee > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.player_token_account.to_account_info(),
+                to: ctx.accounts.fee_token_account.to_account_info(),
+                authority: ctx.accounts.player.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+            token::transfer(cpi_ctx, fee)?;
+
+            let vault = &mut ctx.accounts.vault;
+            vault.total_fees_collected = vault
+                .total_fees_collected
+                .checked_add(fee)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            emit!(ProtocolFeeCollected {
+                player: ctx.accounts.player.key(),
+                mint: ctx.accounts.player_token_account.mint,
+                fee,
+                net_amount,
+            });
+
+            msg!("Protocol fee collected: {}", fee);
+        }
+
+        // Transfer tokens from player to vault
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            to: ctx.accounts.vault_token_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        token::transfer(cpi_ctx, net_amount)?;
+
+        msg!("Treasure hidden successfully");
+
+        // Record hidden treasure in player's PDA
+        let treasure_record = &mut ctx.accounts.treasure_record;
+        treasure_record.player = ctx.accounts.player.key();
+        treasure_record.token_mint = ctx.accounts.token_mint.key();
+        treasure_record.amount = net_amount;
+        treasure_record.remaining = net_amount;
+        treasure_record.vault_id = ctx.accounts.vault.vault_id;
+        treasure_record.source_token_account = ctx.accounts.player_token_account.key();
+        treasure_record.timestamp = treasure_id;
+        treasure_record.claimed = false;
+        treasure_record.bump = ctx.bumps.treasure_record;
+        treasure_record.rewarded = false;
+        treasure_record.found_by = None;
+        treasure_record.reclaimed = false;
+        treasure_record.reward_waived = false;
+        treasure_record.nft_minted = false;
+        treasure_record.note = String::new();
+
+        // Time checks use the on-chain clock (treasure_id is client-supplied)
+        let now = Clock::get()?.unix_timestamp;
+        treasure_record.created_ts = now;
+
+        let claim_window = ctx.accounts.config.claim_window;
+        treasure_record.claim_deadline = if claim_window > 0 {
+            now.checked_add(claim_window)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+        } else {
+            0
+        };
+
+        // Calculate tier from the deposit's USD value when the token has a
+        // price feed, otherwise fall back to the raw token count. Either way the
+        // token's tier multiplier scales the value before it's bucketed
+        let whitelist = &ctx.accounts.whitelist;
+        treasure_record.tier = match whitelist.price_feed {
+            Some(expected_feed) => {
+                let price_feed = ctx
+                    .accounts
+                    .price_feed
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidPriceFeed)?;
+                require_keys_eq!(price_feed.key(), expected_feed, ErrorCode::InvalidPriceFeed);
+
+                let (price, expo) = read_pyth_price(price_feed, whitelist.max_price_age)?;
+                let value_usd = usd_value(net_amount, decimals, price, expo)?;
+                msg!("Treasure value: ${}", value_usd);
+
+                calculate_usd_tier(apply_bps(value_usd, whitelist.tier_multiplier)?)
+            }
+            None => calculate_tier(apply_bps(net_amount, whitelist.tier_multiplier)?, decimals)?,
+        };
+
+        // Slippage protection: revert if config changes since the player's
+        // quote would give them a lower tier than they expected
+        if let Some(expected_tier) = expected_tier {
+            require!(
+                treasure_record.tier >= expected_tier,
+                ErrorCode::TierBelowExpected
+            );
+        }
+
+        // Update vault stats
+        let vault = &mut ctx.accounts.vault;
+        vault.total_hidden = vault
+            .total_hidden
+            .checked_add(net_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // Update player's lifetime stats
+        let profile = &mut ctx.accounts.player_profile;
+        profile.player = ctx.accounts.player.key();
+        profile.bump = ctx.bumps.player_profile;
+        profile.total_hidden = profile
+            .total_hidden
+            .checked_add(net_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        profile.highest_tier = profile.highest_tier.max(treasure_record.tier);
+        profile.total_deposited = total_deposited;
+
+        // Record the player's referrer the first time one is given
+        if let Some(referrer) = referrer {
+            require!(referrer != profile.player, ErrorCode::SelfReferral);
+            match profile.referrer {
+                None => profile.referrer = Some(referrer),
+                Some(existing) => require!(existing == referrer, ErrorCode::ReferrerAlreadySet),
+            }
+        }
+
+        // Update the top-hiders leaderboard
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        leaderboard.bump = ctx.bumps.leaderboard;
+        if let Some(rank) = update_leaderboard(&mut leaderboard.entries, profile.player, profile.total_hidden) {
+            emit!(LeaderboardChanged {
+                player: profile.player,
+                total_hidden: profile.total_hidden,
+                rank,
+            });
+        }
+
+        ctx.accounts.vault.emit_stats();
+
+        msg!("Treasure recorded! Tier: {}", treasure_record.tier);
+        msg!("Player can now claim their premium NFT");
+
+        Ok(())
+    }
+
+    /// Mint a soulbound "proof of deposit" NFT for a hidden treasure
+    /// The vault PDA is mint, freeze and update authority: it freezes the
+    /// player's token account, then the master edition takes over the freeze
+    /// authority, so there is no path to thaw (and transfer) the receipt.
+    /// One receipt per treasure record (the receipt mint is a PDA of the record),
+    /// only while the deposit is still held: not once claimed or reclaimed.
+    /// Kept out of hide_treasure so deposits don't pay for the Metaplex CPIs and
+    /// accounts; clients issue it right after the hide
+    pub fn mint_deposit_receipt(ctx: Context<MintDepositReceipt>, uri: String) -> Result<()> {
+        let amount = ctx.accounts.treasure_record.amount;
+        let created_ts = ctx.accounts.treasure_record.created_ts;
+
+        // Amount is recorded in the on-chain name; the URI's JSON carries the rest
+        let name = format!("Receipt {}", amount);
+        validate_metadata(&name, RECEIPT_SYMBOL, &uri)?;
+
+        let bump = ctx.accounts.vault.bump;
+        let id_seed = ctx.accounts.vault.id_seed();
+        let seeds = &[
+            b"vault".as_ref(),
+            id_seed.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
 
         mint_to(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 MintTo {
-                    mint: ctx.accounts.booty_mint.to_account_info(),
-                    to: ctx.accounts.player_booty_account.to_account_info(),
-                    authority: ctx.accounts.booty_state.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.receipt_token_account.to_account_info(),
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
                 },
                 signer_seeds,
             ),
-            amount,
+            1,
         )?;
 
-        // Update total mined
-        let booty_state = &mut ctx.accounts.booty_state;
-        booty_state.total_mined = booty_state.total_mined
-            .checked_add(amount)
+        // Freeze while the vault still holds the freeze authority
+        token::freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            FreezeAccount {
+                account: ctx.accounts.receipt_token_account.to_account_info(),
+                mint: ctx.accounts.receipt_mint.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        let metadata_data = DataV2 {
+            name,
+            symbol: RECEIPT_SYMBOL.to_string(),
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    payer: ctx.accounts.player.to_account_info(),
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    mint_authority: ctx.accounts.vault.to_account_info(),
+                    update_authority: ctx.accounts.vault.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            metadata_data,
+            false, // is_mutable (a receipt never changes)
+            true,  // update_authority_is_signer
+            None,  // collection_details
+        )?;
+
+        // Moves mint and freeze authority to the edition PDA, ending any thaw path
+        create_master_edition_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMasterEditionV3 {
+                    edition: ctx.accounts.master_edition.to_account_info(),
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    update_authority: ctx.accounts.vault.to_account_info(),
+                    mint_authority: ctx.accounts.vault.to_account_info(),
+                    payer: ctx.accounts.player.to_account_info(),
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            Some(0), // max_supply (no prints allowed)
+        )?;
+
+        emit!(DepositReceiptMinted {
+            player: ctx.accounts.player.key(),
+            treasure: ctx.accounts.treasure_record.key(),
+            mint: ctx.accounts.receipt_mint.key(),
+            amount,
+            created_ts,
+        });
+
+        msg!("Deposit receipt minted: {}", ctx.accounts.receipt_mint.key());
+
+        Ok(())
+    }
+
+    /// Claim hidden treasure to receive premium NFT
+    /// Claims may be taken in installments; the treasure is only marked as
+    /// claimed (and counted in stats) once `remaining` reaches zero
+    pub fn claim_treasure(ctx: Context<ClaimTreasure>, claim_amount: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+
+        let treasure_record = &mut ctx.accounts.treasure_record;
+
+        // Validate not already claimed
+        require!(!treasure_record.claimed, ErrorCode::AlreadyClaimed);
+        require!(!treasure_record.reclaimed, ErrorCode::AlreadyReclaimed);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(!treasure_record.is_expired(now), ErrorCode::ClaimExpired);
+        check_reward_settled(&ctx.accounts.reward_curve, treasure_record)?;
+
+        // Bots can't hide and claim in the same breath
+        let held_for = now
+            .checked_sub(treasure_record.created_ts)
             .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            held_for >= ctx.accounts.config.claim_delay_seconds,
+            ErrorCode::ClaimTooEarly
+        );
+
+        msg!(
+            "Player claiming {} of {} remaining (tier {})",
+            claim_amount,
+            treasure_record.remaining,
+            treasure_record.tier
+        );
+
+        treasure_record.withdraw(claim_amount)?;
+
+        if !treasure_record.claimed {
+            msg!("Partial claim. Remaining: {}", treasure_record.remaining);
+            return Ok(());
+        }
 
-        msg!("Successfully mined {} BOOTY tokens", amount);
-        msg!("Total mined: {}", booty_state.total_mined);
-        msg!("Net supply: {}", booty_state.total_mined - booty_state.total_burned);
+        // Update vault stats
+        let vault = &mut ctx.accounts.vault;
+        vault.total_claimed = vault
+            .total_claimed
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // Update player's lifetime stats
+        let profile = &mut ctx.accounts.player_profile;
+        profile.player = ctx.accounts.player.key();
+        profile.bump = ctx.bumps.player_profile;
+        profile.treasures_claimed = profile
+            .treasures_claimed
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        emit!(TreasureClaimed {
+            player: ctx.accounts.player.key(),
+            tier: treasure_record.tier,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        vault.emit_stats();
+
+        msg!("Treasure claimed! Total claims: {}", vault.total_claimed);
+
+        // Note: Actual NFT minting happens separately via mint_nft instruction
+        // This just validates the player has hidden treasure and tracks the claim
 
         Ok(())
     }
 
-    /// Burn $BOOTY tokens from a player
-    /// Called when a player moves their ship - consumes BOOTY as travel cost
-    pub fn burn_booty_for_travel(
-        ctx: Context<BurnBootyForTravel>,
-        amount: u64,
+    /// Claim several fully-held treasures in one transaction
+    /// remaining_accounts holds the writable TreasureRecords, each claimed in
+    /// full under the same rules as claim_treasure. Any record that fails a
+    /// check (wrong player or vault, already claimed or reclaimed, expired, reward
+    /// pending, too early)
+    /// fails the whole batch
+    pub fn claim_treasure_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimTreasureBatch<'info>>,
     ) -> Result<()> {
-        let booty_state = &mut ctx.accounts.booty_state;
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        let count = ctx.remaining_accounts.len();
+        require!(count > 0, ErrorCode::AccountCountMismatch);
+        require!(count <= MAX_CLAIM_BATCH_SIZE, ErrorCode::BatchTooLarge);
+
+        let player = ctx.accounts.player.key();
+        let vault_id = ctx.accounts.vault.vault_id;
+        let now = Clock::get()?.unix_timestamp;
+
+        for info in ctx.remaining_accounts.iter() {
+            require!(info.is_writable, ErrorCode::InvalidTreasureRecord);
+            let mut treasure_record = Account::<TreasureRecord>::try_from(info)?;
+            require_keys_eq!(treasure_record.player, player, ErrorCode::Unauthorized);
+            require!(treasure_record.vault_id == vault_id, ErrorCode::VaultMismatch);
+            require!(!treasure_record.claimed, ErrorCode::AlreadyClaimed);
+            require!(!treasure_record.reclaimed, ErrorCode::AlreadyReclaimed);
+            require!(!treasure_record.is_expired(now), ErrorCode::ClaimExpired);
+            check_reward_settled(&ctx.accounts.reward_curve, &treasure_record)?;
+
+            let held_for = now
+                .checked_sub(treasure_record.created_ts)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            require!(
+                held_for >= ctx.accounts.config.claim_delay_seconds,
+                ErrorCode::ClaimTooEarly
+            );
 
-        msg!("Burning {} BOOTY tokens from player {} for travel", amount, ctx.accounts.player.key());
+            let remaining = treasure_record.remaining;
+            treasure_record.withdraw(remaining)?;
+            // Written back immediately so a duplicate entry fails as AlreadyClaimed
+            treasure_record.exit(&crate::ID)?;
 
-        // Burn tokens from player's account
-        burn(
+            emit!(TreasureClaimed {
+                player,
+                tier: treasure_record.tier,
+                timestamp: now,
+            });
+        }
+
+        // Update vault stats once for the whole batch
+        let vault = &mut ctx.accounts.vault;
+        vault.total_claimed = vault
+            .total_claimed
+            .checked_add(count as u64)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // Update player's lifetime stats
+        let profile = &mut ctx.accounts.player_profile;
+        profile.player = player;
+        profile.bump = ctx.bumps.player_profile;
+        profile.treasures_claimed = profile
+            .treasures_claimed
+            .checked_add(count as u64)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        vault.emit_stats();
+
+        msg!("{} treasures claimed! Total claims: {}", count, vault.total_claimed);
+
+        Ok(())
+    }
+
+    /// Admin escape hatch for stuck treasure (e.g. a player lost their wallet)
+    /// Marks the record claimed on the player's behalf; the backend mints the
+    /// NFT to `destination` from the emitted event. reason_code is free-form
+    /// for support audits
+    pub fn admin_force_claim(
+        ctx: Context<AdminForceClaim>,
+        destination: Pubkey,
+        reason_code: u8,
+    ) -> Result<()> {
+        let treasure_record = &mut ctx.accounts.treasure_record;
+        let remaining = treasure_record.remaining;
+        treasure_record.withdraw(remaining)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.total_claimed = vault
+            .total_claimed
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        vault.emit_stats();
+
+        emit!(TreasureForceClaimed {
+            treasure_record: treasure_record.key(),
+            player: treasure_record.player,
+            destination,
+            authority: ctx.accounts.authority.key(),
+            reason_code,
+        });
+
+        msg!(
+            "Treasure {} force-claimed to {} (reason {})",
+            treasure_record.key(),
+            destination,
+            reason_code
+        );
+
+        Ok(())
+    }
+
+    /// Give up the BOOTY reward for a treasure so it can be claimed without
+    /// mining first (e.g. when the max supply leaves nothing to mint)
+    /// Nothing is minted, so the deposit can still be reclaimed afterwards
+    pub fn waive_treasure_reward(ctx: Context<WaiveTreasureReward>) -> Result<()> {
+        ctx.accounts.treasure_record.reward_waived = true;
+
+        msg!("BOOTY reward waived for {}", ctx.accounts.treasure_record.key());
+
+        Ok(())
+    }
+
+    /// Report whether a treasure can be claimed right now, without side effects
+    /// Applies the same rules as claim_treasure; clients simulate this and
+    /// decode the returned ClaimableStatus (reason is one of the CLAIM_STATUS_* codes)
+    pub fn check_claimable(ctx: Context<CheckClaimable>) -> Result<ClaimableStatus> {
+        let treasure_record = &ctx.accounts.treasure_record;
+        let config = &ctx.accounts.config;
+        let now = Clock::get()?.unix_timestamp;
+
+        let reason = if treasure_record.claimed {
+            CLAIM_STATUS_ALREADY_CLAIMED
+        } else if treasure_record.reclaimed {
+            CLAIM_STATUS_RECLAIMED
+        } else if config.paused {
+            CLAIM_STATUS_PAUSED
+        } else if treasure_record.is_expired(now) {
+            CLAIM_STATUS_EXPIRED
+        } else if !reward_settled(&ctx.accounts.reward_curve, treasure_record) {
+            CLAIM_STATUS_REWARD_PENDING
+        } else if now.saturating_sub(treasure_record.created_ts) < config.claim_delay_seconds {
+            CLAIM_STATUS_TOO_EARLY
+        } else {
+            CLAIM_STATUS_CLAIMABLE
+        };
+
+        Ok(ClaimableStatus {
+            claimable: reason == CLAIM_STATUS_CLAIMABLE,
+            reason,
+        })
+    }
+
+    /// Attach a short note (e.g. a map-region tag) to a hidden treasure
+    /// The record is resized to fit; the player pays or is refunded the rent difference
+    pub fn annotate_treasure(ctx: Context<AnnotateTreasure>, note: String) -> Result<()> {
+        require!(note.len() <= MAX_NOTE_LEN, ErrorCode::NoteTooLong);
+
+        ctx.accounts.treasure_record.note = note;
+
+        msg!("Treasure note updated");
+
+        Ok(())
+    }
+
+    /// Reclaim unclaimed treasure - returns the hidden tokens to the player
+    /// May be done in installments; once nothing remains the treasure record
+    /// is closed and its rent refunded to the player. Treasure BOOTY has been
+    /// mined for can't be reclaimed, or the same deposit could be re-hidden
+    /// and mined again
+    pub fn reclaim_treasure(ctx: Context<ReclaimTreasure>, claim_amount: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+
+        // Expired treasure is forfeit and can only be swept by the authority
+        require!(
+            !ctx.accounts.treasure_record.is_expired(Clock::get()?.unix_timestamp),
+            ErrorCode::ClaimExpired
+        );
+
+        let amount = claim_amount;
+        require!(
+            ctx.accounts.vault_token_account.amount >= amount,
+            ErrorCode::InsufficientVaultBalance
+        );
+
+        msg!("Player reclaiming {} hidden tokens", amount);
+
+        // Checks-effects-interactions: consume the record and debit the vault
+        // BEFORE the transfer CPI, and close the record last.
+        // Do not move these writes below the transfer.
+        ctx.accounts.treasure_record.withdraw(amount)?;
+        ctx.accounts.treasure_record.reclaimed = true;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.debit_hidden(amount)?;
+
+        let bump = vault.bump;
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault".as_ref(),
+            id_seed.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    to: ctx.accounts.player_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(TreasureReclaimed {
+            player: ctx.accounts.player.key(),
+            mint: ctx.accounts.treasure_record.token_mint,
+            amount,
+        });
+
+        ctx.accounts.vault.emit_stats();
+
+        msg!("Treasure reclaimed. Total hidden: {}", ctx.accounts.vault.total_hidden);
+
+        if ctx.accounts.treasure_record.claimed {
+            ctx.accounts
+                .treasure_record
+                .close(ctx.accounts.player.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    // ====================================================================
+    // TREASURE SEARCH SYSTEM
+    // ====================================================================
+
+    /// Search for treasure at specific map coordinates
+    /// Player submits a search transaction with x,y coordinates
+    /// This creates a searchable record that the monitoring service can detect
+    pub fn search_treasure(
+        ctx: Context<SearchTreasure>,
+        x: i32,
+        y: i32,
+        search_id: i64,
+    ) -> Result<()> {
+        msg!("Player searching for treasure at coordinates ({}, {})", x, y);
+
+        let enforce_ids = ctx.accounts.config.require_monotonic_ids;
+        ctx.accounts
+            .player_profile
+            .record_search_id(search_id, enforce_ids)?;
+
+        // Charge 1 BOOTY token for the search
+        let search_fee = booty_amount(SEARCH_FEE_TOKENS, ctx.accounts.booty_state.decimals)?;
+        msg!("Charging {} BOOTY tokens for search", SEARCH_FEE_TOKENS);
+
+        // Transfer BOOTY tokens from player to vault (Token or Token-2022 mint)
+        token_interface::transfer_checked(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.booty_mint.to_account_info(),
+                TransferChecked {
                     from: ctx.accounts.player_booty_account.to_account_info(),
+                    mint: ctx.accounts.booty_mint.to_account_info(),
+                    to: ctx.accounts.vault_booty_account.to_account_info(),
                     authority: ctx.accounts.player.to_account_info(),
                 },
             ),
-            amount,
+            search_fee,
+            ctx.accounts.booty_mint.decimals,
         )?;
 
-        // Update total burned
-        booty_state.total_burned = booty_state.total_burned
-            .checked_add(amount)
+        msg!("Search fee paid successfully");
+
+        // Record the search in player's PDA
+        let search_record = &mut ctx.accounts.search_record;
+        search_record.player = ctx.accounts.player.key();
+        search_record.x = x;
+        search_record.y = y;
+        search_record.timestamp = search_id;
+        search_record.found = false;
+        search_record.bump = ctx.bumps.search_record;
+        search_record.created_ts = Clock::get()?.unix_timestamp;
+
+        // Update player's lifetime stats
+        let profile = &mut ctx.accounts.player_profile;
+        profile.player = ctx.accounts.player.key();
+        profile.bump = ctx.bumps.player_profile;
+        profile.searches_made = profile
+            .searches_made
+            .checked_add(1)
             .ok_or(ErrorCode::ArithmeticOverflow)?;
 
-        msg!("Successfully burned {} BOOTY tokens", amount);
-        msg!("Total burned: {}", booty_state.total_burned);
-        msg!("Net supply: {}", booty_state.total_mined - booty_state.total_burned);
+        msg!("Search recorded at ({}, {})", x, y);
+        msg!("Search ID: {}", search_id);
 
         Ok(())
     }
+
+    /// Search several map coordinates in one transaction
+    /// remaining_accounts holds one uninitialized search record PDA per
+    /// coordinate, derived from `base_search_id + index`; the whole batch's
+    /// fee is charged up front so it succeeds or fails atomically
+    pub fn search_treasure_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SearchTreasureBatch<'info>>,
+        coords: Vec<SearchCoord>,
+        base_search_id: i64,
+    ) -> Result<()> {
+        require!(!coords.is_empty(), ErrorCode::AccountCountMismatch);
+        require!(coords.len() <= MAX_SEARCH_BATCH_SIZE, ErrorCode::BatchTooLarge);
+        require!(
+            ctx.remaining_accounts.len() == coords.len(),
+            ErrorCode::AccountCountMismatch
+        );
+
+        msg!("Player searching {} coordinates", coords.len());
+
+        let enforce_ids = ctx.accounts.config.require_monotonic_ids;
+
+        // Charge 1 BOOTY token per searched coordinate in a single transfer
+        let batch_fee = booty_amount(SEARCH_FEE_TOKENS, ctx.accounts.booty_state.decimals)?
+            .checked_mul(coords.len() as u64)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.player_booty_account.to_account_info(),
+                    mint: ctx.accounts.booty_mint.to_account_info(),
+                    to: ctx.accounts.vault_booty_account.to_account_info(),
+                    authority: ctx.accounts.player.to_account_info(),
+                },
+            ),
+            batch_fee,
+            ctx.accounts.booty_mint.decimals,
+        )?;
+
+        msg!("Search fees paid: {}", batch_fee);
+
+        for (index, (coord, search_record)) in coords
+            .iter()
+            .zip(ctx.remaining_accounts.iter())
+            .enumerate()
+        {
+            let search_id = base_search_id
+                .checked_add(index as i64)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            // remaining_accounts are not initialized by Anchor, so create them here
+            ctx.accounts
+                .init_search_record(search_record, coord, search_id)?;
+            ctx.accounts
+                .player_profile
+                .record_search_id(search_id, enforce_ids)?;
+
+            msg!("Search recorded at ({}, {}), ID {}", coord.x, coord.y, search_id);
+        }
+
+        // Update player's lifetime stats
+        let profile = &mut ctx.accounts.player_profile;
+        profile.player = ctx.accounts.player.key();
+        profile.bump = ctx.bumps.player_profile;
+        profile.searches_made = profile
+            .searches_made
+            .checked_add(coords.len() as u64)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Prove the signing player holds a game collectible with `expected_symbol`
+    /// Has no side effects; clients place it ahead of NFT-gated actions in the
+    /// same transaction so the whole transaction fails for non-holders
+    pub fn verify_collectible_ownership(
+        ctx: Context<VerifyCollectibleOwnership>,
+        expected_symbol: String,
+    ) -> Result<()> {
+        require_collectible_ownership(
+            &ctx.accounts.token_account,
+            &ctx.accounts.metadata,
+            &ctx.accounts.minter_registry,
+            ctx.accounts.player.key(),
+            &expected_symbol,
+        )?;
+
+        msg!("Player {} holds a {} collectible", ctx.accounts.player.key(), expected_symbol);
+
+        Ok(())
+    }
+
+    /// Spend one use of a consumable collectible (e.g. a map scroll) for a free search
+    /// Metaplex decrements the remaining uses and burns the NFT on its last use
+    pub fn use_collectible(
+        ctx: Context<UseCollectible>,
+        x: i32,
+        y: i32,
+        search_id: i64,
+    ) -> Result<()> {
+        let remaining = ctx
+            .accounts
+            .metadata
+            .uses
+            .as_ref()
+            .map(|uses| uses.remaining)
+            .ok_or(ErrorCode::NotConsumable)?;
+        require!(remaining > 0, ErrorCode::NotConsumable);
+
+        msg!("Using collectible {} for a search at ({}, {})", ctx.accounts.mint.key(), x, y);
+
+        // The owner is also the use authority, so no use authority record is needed
+        utilize(
+            CpiContext::new(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                Utilize {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    token_account: ctx.accounts.token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    use_authority: ctx.accounts.player.to_account_info(),
+                    owner: ctx.accounts.player.to_account_info(),
+                },
+            ),
+            None, // use_authority_record
+            None, // burner
+            1,
+        )?;
+
+        // Record the free search exactly like a paid one
+        let search_record = &mut ctx.accounts.search_record;
+        search_record.player = ctx.accounts.player.key();
+        search_record.x = x;
+        search_record.y = y;
+        search_record.timestamp = search_id;
+        search_record.found = false;
+        search_record.bump = ctx.bumps.search_record;
+        search_record.created_ts = Clock::get()?.unix_timestamp;
+
+        let profile = &mut ctx.accounts.player_profile;
+        profile.player = ctx.accounts.player.key();
+        profile.bump = ctx.bumps.player_profile;
+        profile.searches_made = profile
+            .searches_made
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        emit!(CollectibleUsed {
+            player: ctx.accounts.player.key(),
+            mint: ctx.accounts.mint.key(),
+            remaining: remaining - 1,
+        });
+
+        msg!("Free search recorded, {} uses left", remaining - 1);
+
+        Ok(())
+    }
+
+    /// Admin function to confirm a find detected by the monitoring service
+    /// Marks the search as found and mints the BOOTY reward to the player,
+    /// emitting an event so every off-chain decision has an on-chain record.
+    /// Each treasure has one first finder: verifications that write the same
+    /// treasure record are serialized by the runtime, so the first to land wins
+    pub fn verify_find(ctx: Context<VerifyFind>, search_id: i64) -> Result<()> {
+        require!(!ctx.accounts.search_record.found, ErrorCode::AlreadyFound);
+        require!(ctx.accounts.treasure_record.found_by.is_none(), ErrorCode::AlreadyFound);
+
+        let find_reward = booty_amount(FIND_REWARD_TOKENS, ctx.accounts.booty_state.decimals)?;
+
+        // Check max supply if set
+        if let Some(max_supply) = ctx.accounts.booty_state.max_supply {
+            let new_total = ctx.accounts.booty_state.total_mined
+                .checked_add(find_reward)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            require!(
+                new_total <= max_supply,
+                ErrorCode::MaxSupplyExceeded
+            );
+        }
+
+        let bump = ctx.accounts.booty_state.bump;
+        let seeds = &[
+            b"booty-state".as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.booty_mint.to_account_info(),
+                    to: ctx.accounts.player_booty_account.to_account_info(),
+                    authority: ctx.accounts.booty_state.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            find_reward,
+        )?;
+
+        let booty_state = &mut ctx.accounts.booty_state;
+        booty_state.total_mined = booty_state.total_mined
+            .checked_add(find_reward)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let search_record = &mut ctx.accounts.search_record;
+        search_record.found = true;
+        ctx.accounts.treasure_record.found_by = Some(search_record.player);
+
+        emit!(FindVerified {
+            authority: ctx.accounts.authority.key(),
+            player: search_record.player,
+            treasure: ctx.accounts.treasure_record.key(),
+            search_id,
+            x: search_record.x,
+            y: search_record.y,
+            reward: find_reward,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Find verified for search {} at ({}, {})", search_id, search_record.x, search_record.y);
+        msg!("Rewarded {} BOOTY to {}", find_reward, search_record.player);
+
+        Ok(())
+    }
+
+    /// Admin function to whitelist a token mint
+    /// This allows adding new tokens that can be hidden as treasure
+    pub fn whitelist_token(
+        ctx: Context<WhitelistToken>,
+        token_mint: Pubkey,
+    ) -> Result<()> {
+        require!(
+            token_mint == ctx.accounts.token_mint.key(),
+            ErrorCode::InvalidTokenAccount
+        );
+
+        let whitelist = &mut ctx.accounts.whitelist;
+        whitelist.set_inner(TokenWhitelist::new(
+            token_mint,
+            ctx.accounts.token_mint.decimals,
+            ctx.bumps.whitelist,
+        ));
+
+        let index = &mut ctx.accounts.whitelist_index;
+        index.bump = ctx.bumps.whitelist_index;
+        index.insert(token_mint)?;
+
+        msg!("Token whitelisted: {}", token_mint);
+        msg!("Decimals: {}", whitelist.decimals);
+
+        Ok(())
+    }
+
+    /// Admin function to whitelist several token mints in one transaction
+    /// remaining_accounts holds WHITELIST_BATCH_ACCOUNTS entries per mint, in order:
+    /// [whitelist PDA (uninitialized), mint]; seeds match whitelist_token
+    pub fn whitelist_tokens_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WhitelistTokensBatch<'info>>,
+        token_mints: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(!token_mints.is_empty(), ErrorCode::AccountCountMismatch);
+        require!(token_mints.len() <= MAX_WHITELIST_BATCH_SIZE, ErrorCode::BatchTooLarge);
+        require!(
+            ctx.remaining_accounts.len() == token_mints.len() * WHITELIST_BATCH_ACCOUNTS,
+            ErrorCode::AccountCountMismatch
+        );
+
+        for (token_mint, accounts) in token_mints
+            .iter()
+            .zip(ctx.remaining_accounts.chunks(WHITELIST_BATCH_ACCOUNTS))
+        {
+            require_keys_eq!(accounts[1].key(), *token_mint, ErrorCode::MintMismatch);
+
+            // remaining_accounts are not initialized by Anchor, so create them here
+            let decimals = ctx
+                .accounts
+                .init_whitelist_entry(&accounts[0], &accounts[1])?;
+
+            ctx.accounts.whitelist_index.insert(*token_mint)?;
+
+            msg!("Token whitelisted: {} ({} decimals)", token_mint, decimals);
+        }
+
+        ctx.accounts.whitelist_index.bump = ctx.bumps.whitelist_index;
+
+        Ok(())
+    }
+
+    /// Admin function to enable or disable a whitelisted token
+    /// Lets admins delist a token that turns out to be malicious
+    pub fn set_whitelist_enabled(
+        ctx: Context<SetWhitelistEnabled>,
+        enabled: bool,
+    ) -> Result<()> {
+        let whitelist = &mut ctx.accounts.whitelist;
+        whitelist.enabled = enabled;
+
+        let index = &mut ctx.accounts.whitelist_index;
+        index.bump = ctx.bumps.whitelist_index;
+        if enabled {
+            index.insert(whitelist.token_mint)?;
+        } else {
+            index.remove(&whitelist.token_mint);
+        }
+
+        msg!("Token {} whitelist enabled: {}", whitelist.token_mint, enabled);
+
+        Ok(())
+    }
+
+    /// Admin function to remove a token from the whitelist entirely
+    /// Closes the whitelist PDA and refunds its rent to the authority; the
+    /// entry must be disabled with set_whitelist_enabled first
+    pub fn close_whitelist_entry(ctx: Context<CloseWhitelistEntry>) -> Result<()> {
+        ctx.accounts
+            .whitelist_index
+            .remove(&ctx.accounts.whitelist.token_mint);
+
+        msg!("Token removed from whitelist: {}", ctx.accounts.whitelist.token_mint);
+
+        Ok(())
+    }
+
+    /// Admin function to set (or clear) a whitelisted token's Pyth price feed
+    /// With a feed set, treasure tiers reflect the deposit's USD value so a
+    /// near-worthless token can't buy a high tier by volume alone
+    pub fn set_price_feed(
+        ctx: Context<UpdateWhitelist>,
+        price_feed: Option<Pubkey>,
+        max_price_age: u64,
+    ) -> Result<()> {
+        let whitelist = &mut ctx.accounts.whitelist;
+        whitelist.price_feed = price_feed;
+        whitelist.max_price_age = max_price_age;
+
+        match price_feed {
+            Some(feed) => msg!("Token {} price feed set to {}", whitelist.token_mint, feed),
+            None => msg!("Token {} price feed cleared", whitelist.token_mint),
+        }
+        msg!("Max price age: {} seconds", max_price_age);
+
+        Ok(())
+    }
+
+    /// Admin function to tune a whitelisted token's economics
+    /// `min_deposit_override` (base units) replaces the global minimum when set;
+    /// `tier_multiplier` (bps, 10,000 = neutral) scales deposits before tiering
+    pub fn set_whitelist_params(
+        ctx: Context<UpdateWhitelist>,
+        min_deposit_override: Option<u64>,
+        tier_multiplier: u16,
+    ) -> Result<()> {
+        require!(
+            tier_multiplier > 0 && tier_multiplier <= MAX_TIER_MULTIPLIER_BPS,
+            ErrorCode::InvalidTierMultiplier
+        );
+
+        let whitelist = &mut ctx.accounts.whitelist;
+        whitelist.min_deposit_override = min_deposit_override;
+        whitelist.tier_multiplier = tier_multiplier;
+
+        match min_deposit_override {
+            Some(min) => msg!("Token {} minimum deposit set to {}", whitelist.token_mint, min),
+            None => msg!("Token {} uses the global minimum deposit", whitelist.token_mint),
+        }
+        msg!("Tier multiplier: {} bps", tier_multiplier);
+
+        Ok(())
+    }
+
+    /// Admin function to re-read a whitelisted token's decimals from its mint
+    /// Backfills entries created before `decimals` existed (or with a stale value)
+    /// without closing and recreating them
+    pub fn refresh_whitelist_decimals(ctx: Context<RefreshWhitelistDecimals>) -> Result<()> {
+        let info = ctx.accounts.whitelist.to_account_info();
+        let mut whitelist = TokenWhitelist::read_any_layout(&info)?;
+
+        // The entry is unchecked so legacy layouts load; verify it is the
+        // whitelist PDA of the mint it names, and that mint is the one passed
+        let expected = Pubkey::create_program_address(
+            &[b"whitelist", whitelist.token_mint.as_ref(), &[whitelist.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidMigrationTarget)?;
+        require_keys_eq!(expected, info.key(), ErrorCode::InvalidMigrationTarget);
+        require_keys_eq!(
+            ctx.accounts.token_mint.key(),
+            whitelist.token_mint,
+            ErrorCode::MintMismatch
+        );
+
+        let old_decimals = whitelist.decimals;
+        whitelist.decimals = ctx.accounts.token_mint.decimals;
+
+        if info.data_len() < TokenWhitelist::LEN {
+            grow_account(
+                &info,
+                TokenWhitelist::LEN,
+                &ctx.accounts.authority.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        let mut data = info.try_borrow_mut_data()?;
+        whitelist.try_serialize(&mut &mut data[..])?;
+
+        msg!(
+            "Token {} decimals refreshed: {} -> {}",
+            whitelist.token_mint,
+            old_decimals,
+            whitelist.decimals
+        );
+
+        Ok(())
+    }
+
+    /// Admin function to re-read the BOOTY decimals from the mint
+    /// Backfills BOOTY states migrated from before decimals were stored
+    pub fn refresh_booty_decimals(ctx: Context<RefreshBootyDecimals>) -> Result<()> {
+        let booty_state = &mut ctx.accounts.booty_state;
+        let old_decimals = booty_state.decimals;
+        booty_state.decimals = ctx.accounts.booty_mint.decimals;
+
+        msg!("BOOTY decimals refreshed: {} -> {}", old_decimals, booty_state.decimals);
+
+        Ok(())
+    }
+
+    /// Admin function to update vault settings
+    /// A new authority is only proposed here - it takes effect once the new
+    /// key signs accept_authority, so a typo can't lock the admin out
+    pub fn update_vault(
+        ctx: Context<UpdateConfig>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        if let Some(authority) = new_authority {
+            config.pending_authority = Some(authority);
+            msg!("Vault authority transfer proposed to: {}", authority);
+        }
+
+        Ok(())
+    }
+
+    /// Accept a proposed vault authority transfer
+    /// Must be signed by the pending authority set in update_vault
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.new_authority.key();
+        config.pending_authority = None;
+
+        msg!("Vault authority updated to: {}", config.authority);
+
+        Ok(())
+    }
+
+    /// Admin function to cancel a pending authority transfer
+    pub fn cancel_authority_transfer(ctx: Context<UpdateConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(config.pending_authority.is_some(), ErrorCode::NoPendingAuthority);
+        config.pending_authority = None;
+
+        msg!("Vault authority transfer cancelled");
+
+        Ok(())
+    }
+
+    /// Admin function to hand a vault token account to a new owner, e.g. the
+    /// vault PDA of an upgraded program during a migration
+    /// The program must be paused so no deposit lands in an account the vault
+    /// no longer controls
+    pub fn reassign_vault_token_authority(
+        ctx: Context<ReassignVaultTokenAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        require!(ctx.accounts.config.paused, ErrorCode::ProgramNotPaused);
+
+        let vault = &ctx.accounts.vault;
+        let bump = vault.bump;
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault".as_ref(),
+            id_seed.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.vault.to_account_info(),
+                    account_or_mint: ctx.accounts.vault_token_account.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::AccountOwner,
+            Some(new_authority),
+        )?;
+
+        emit!(VaultTokenAuthorityChanged {
+            token_account: ctx.accounts.vault_token_account.key(),
+            old_authority: ctx.accounts.vault.key(),
+            new_authority,
+        });
+
+        msg!(
+            "Vault token account {} reassigned to {}",
+            ctx.accounts.vault_token_account.key(),
+            new_authority
+        );
+
+        Ok(())
+    }
+
+    /// Admin function to start an emergency drain of one vault token account
+    /// The drain can only execute DRAIN_TIMELOCK_SECONDS later, giving players
+    /// time to react; cancel_drain aborts it
+    pub fn propose_drain(ctx: Context<ProposeDrain>) -> Result<()> {
+        let unlock_ts = Clock::get()?
+            .unix_timestamp
+            .checked_add(DRAIN_TIMELOCK_SECONDS)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let proposal = &mut ctx.accounts.drain_proposal;
+        proposal.vault_token_account = ctx.accounts.vault_token_account.key();
+        proposal.destination = ctx.accounts.destination_token_account.key();
+        proposal.unlock_ts = unlock_ts;
+        proposal.bump = ctx.bumps.drain_proposal;
+
+        emit!(DrainProposed {
+            vault_token_account: proposal.vault_token_account,
+            destination: proposal.destination,
+            unlock_ts,
+        });
+
+        msg!("Drain of {} proposed, unlocks at {}", proposal.vault_token_account, unlock_ts);
+
+        Ok(())
+    }
+
+    /// Admin function to move the full balance of a vault token account to the
+    /// proposed destination once the drain timelock has elapsed
+    /// Drained tokens come off the vault's hidden totals (up to what is owed)
+    pub fn execute_drain(ctx: Context<ExecuteDrain>) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= ctx.accounts.drain_proposal.unlock_ts,
+            ErrorCode::DrainTimelockActive
+        );
+
+        let amount = ctx.accounts.vault_token_account.amount;
+        let vault = &mut ctx.accounts.vault;
+        let owed = vault
+            .total_hidden
+            .checked_add(vault.archived_hidden)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        vault.debit_hidden(amount.min(owed))?;
+        vault.emit_stats();
+
+        let bump = vault.bump;
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault".as_ref(),
+            id_seed.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    to: ctx.accounts.destination_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(VaultDrained {
+            vault_token_account: ctx.accounts.vault_token_account.key(),
+            destination: ctx.accounts.destination_token_account.key(),
+            amount,
+        });
+
+        msg!("Drained {} tokens to {}", amount, ctx.accounts.destination_token_account.key());
+
+        Ok(())
+    }
+
+    /// Admin function to abort a pending drain
+    pub fn cancel_drain(ctx: Context<CancelDrain>) -> Result<()> {
+        msg!("Drain of {} cancelled", ctx.accounts.drain_proposal.vault_token_account);
+
+        Ok(())
+    }
+
+    /// Admin function to withdraw deposited tokens from the vault (treasury operations)
+    /// The vault PDA signs the transfer; accounting is checked so the admin
+    /// can never withdraw more than is recorded as hidden
+    pub fn withdraw_vault_tokens(
+        ctx: Context<WithdrawVaultTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.vault_token_account.amount >= amount,
+            ErrorCode::InsufficientVaultBalance
+        );
+
+        let vault = &mut ctx.accounts.vault;
+        vault.debit_hidden(amount)?;
+
+        msg!("Withdrawing {} tokens from vault", amount);
+
+        let bump = vault.bump;
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault".as_ref(),
+            id_seed.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    to: ctx.accounts.destination_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(VaultWithdrawal {
+            authority: ctx.accounts.authority.key(),
+            destination: ctx.accounts.destination_token_account.key(),
+            amount,
+            total_hidden: ctx.accounts.vault.total_hidden,
+        });
+
+        ctx.accounts.vault.emit_stats();
+
+        msg!("Withdrawal complete. Total hidden: {}", ctx.accounts.vault.total_hidden);
+
+        Ok(())
+    }
+
+    /// Admin function to permanently destroy deposited treasure tokens
+    /// (deflationary events). The vault PDA owns the token account, so it
+    /// signs the burn; the burned amount comes out of total_hidden
+    pub fn burn_vault_tokens(
+        ctx: Context<BurnVaultTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.vault_token_account.amount >= amount,
+            ErrorCode::InsufficientVaultBalance
+        );
+
+        let vault = &mut ctx.accounts.vault;
+        vault.debit_hidden(amount)?;
+        vault.total_burned_treasure = vault
+            .total_burned_treasure
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Burning {} tokens from vault", amount);
+
+        let bump = vault.bump;
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault".as_ref(),
+            id_seed.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(TreasureBurned {
+            mint: ctx.accounts.token_mint.key(),
+            amount,
+            total_burned_treasure: ctx.accounts.vault.total_burned_treasure,
+        });
+
+        ctx.accounts.vault.emit_stats();
+
+        msg!("Burn complete. Total hidden: {}", ctx.accounts.vault.total_hidden);
+
+        Ok(())
+    }
+
+    /// Admin function to set how long new treasures stay claimable (0 = forever)
+    /// Only affects treasures hidden after the change
+    pub fn set_claim_window(ctx: Context<UpdateConfig>, claim_window: i64) -> Result<()> {
+        require!(claim_window >= 0, ErrorCode::InvalidClaimWindow);
+
+        let config = &mut ctx.accounts.config;
+        config.claim_window = claim_window;
+
+        msg!("Claim window set to {} seconds", claim_window);
+
+        Ok(())
+    }
+
+    /// Admin function to set the minimum time between hiding and claiming
+    pub fn set_claim_delay(ctx: Context<UpdateConfig>, claim_delay_seconds: i64) -> Result<()> {
+        require!(claim_delay_seconds >= 0, ErrorCode::InvalidClaimDelay);
+
+        let config = &mut ctx.accounts.config;
+        config.claim_delay_seconds = claim_delay_seconds;
+
+        msg!("Claim delay set to {} seconds", claim_delay_seconds);
+
+        Ok(())
+    }
+
+    /// Admin function to require each player's search_ids and treasure_ids to
+    /// strictly increase, giving indexers a reliable per-player ordering
+    pub fn set_require_monotonic_ids(ctx: Context<UpdateConfig>, required: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.require_monotonic_ids = required;
+
+        msg!("Monotonic ids required: {}", required);
+
+        Ok(())
+    }
+
+    /// Admin function to forfeit an expired, unclaimed treasure
+    /// Its tokens move to the protocol fee account for the mint (the treasury)
+    /// and the record is closed, refunding its rent to the player who hid it
+    pub fn sweep_expired(ctx: Context<SweepExpired>) -> Result<()> {
+        require!(
+            ctx.accounts.treasure_record.is_expired(Clock::get()?.unix_timestamp),
+            ErrorCode::ClaimNotExpired
+        );
+
+        // Only the unclaimed part is forfeit; installments already paid stay paid
+        let amount = ctx.accounts.treasure_record.remaining;
+        require!(
+            ctx.accounts.vault_token_account.amount >= amount,
+            ErrorCode::InsufficientVaultBalance
+        );
+
+        // Checks-effects-interactions: consume the record and debit the vault
+        // BEFORE the transfer CPI; Anchor closes the record last (on exit)
+        ctx.accounts.treasure_record.withdraw(amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.debit_hidden(amount)?;
+
+        // Forfeited tokens become protocol revenue, withdrawable via withdraw_fees
+        vault.total_fees_collected = vault
+            .total_fees_collected
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let bump = vault.bump;
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault".as_ref(),
+            id_seed.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    to: ctx.accounts.fee_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(TreasureForfeited {
+            player: ctx.accounts.treasure_record.player,
+            mint: ctx.accounts.treasure_record.token_mint,
+            amount,
+        });
+
+        ctx.accounts.vault.emit_stats();
+
+        msg!("Swept {} expired tokens to the treasury", amount);
+
+        Ok(())
+    }
+
+    /// Admin function to move unattributed dust (rounding leftovers, direct
+    /// transfers) from a vault token account into the mint's fee account
+    /// The vault only tracks holdings owed across all mints, so dust can only be
+    /// measured for a single-mint game: the sweep is refused unless this mint is
+    /// the only one whitelisted, and dust is the balance beyond what is owed
+    pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+        require!(
+            ctx.accounts.whitelist_index.mints == [ctx.accounts.vault_token_account.mint],
+            ErrorCode::MultiMintVault
+        );
+
+        let vault = &mut ctx.accounts.vault;
+        let owed = vault
+            .total_hidden
+            .checked_add(vault.archived_hidden)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let dust = ctx.accounts.vault_token_account.amount.saturating_sub(owed);
+        require!(dust > 0, ErrorCode::NoDust);
+
+        // Swept dust becomes protocol revenue, withdrawable via withdraw_fees
+        vault.total_fees_collected = vault
+            .total_fees_collected
+            .checked_add(dust)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let bump = vault.bump;
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault".as_ref(),
+            id_seed.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    to: ctx.accounts.fee_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            dust,
+        )?;
+
+        emit!(DustSwept {
+            mint: ctx.accounts.vault_token_account.mint,
+            amount: dust,
+        });
+
+        msg!("Swept {} dust to the treasury", dust);
+
+        Ok(())
+    }
+
+    /// Admin function to withdraw accrued protocol fees
+    /// Draws only on a mint's fee account, never on player deposits; the
+    /// vault tracks fees collected vs. withdrawn so revenue is auditable
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        require!(
+            ctx.accounts.fee_token_account.amount >= amount,
+            ErrorCode::InsufficientVaultBalance
+        );
+
+        let vault = &mut ctx.accounts.vault;
+        let total_fees_withdrawn = vault
+            .total_fees_withdrawn
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            total_fees_withdrawn <= vault.total_fees_collected,
+            ErrorCode::InsufficientFees
+        );
+        vault.total_fees_withdrawn = total_fees_withdrawn;
+
+        let bump = vault.bump;
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault".as_ref(),
+            id_seed.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.fee_token_account.to_account_info(),
+                    to: ctx.accounts.destination_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(FeesWithdrawn {
+            authority: ctx.accounts.authority.key(),
+            mint: ctx.accounts.fee_token_account.mint,
+            destination: ctx.accounts.destination_token_account.key(),
+            amount,
+            total_fees_withdrawn,
+        });
+
+        msg!("Withdrew {} in fees. Total withdrawn: {}", amount, total_fees_withdrawn);
+
+        Ok(())
+    }
+
+    /// Admin function to decommission a vault
+    /// Only allowed once all treasure has been reclaimed or withdrawn;
+    /// closes the vault PDA and refunds its rent to the authority. The
+    /// ProgramConfig is shared by every vault and stays open
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+        require!(ctx.accounts.vault.total_hidden == 0, ErrorCode::VaultNotEmpty);
+        require!(ctx.accounts.vault.archived_hidden == 0, ErrorCode::VaultNotEmpty);
+        require!(ctx.accounts.vault_token_account.amount == 0, ErrorCode::VaultNotEmpty);
+
+        msg!("Vault closed by {}", ctx.accounts.authority.key());
+
+        Ok(())
+    }
+
+    /// Admin function to end the current season and start the next one
+    /// Season tallies are emitted for off-chain archival, then reset; tokens
+    /// still owed from the ended season move to archived_hidden
+    pub fn start_new_season(ctx: Context<UpdateVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        emit!(SeasonEnded {
+            season: vault.season,
+            total_hidden: vault.total_hidden,
+            total_claimed: vault.total_claimed,
+        });
+
+        vault.archived_hidden = vault
+            .archived_hidden
+            .checked_add(vault.total_hidden)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        vault.total_hidden = 0;
+        vault.total_claimed = 0;
+        vault.season = vault
+            .season
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        vault.emit_stats();
+
+        msg!("Season {} started", vault.season);
+
+        Ok(())
+    }
+
+    /// Admin function to upgrade a TreasureVault or BootyState to the current layout
+    /// The stored layout version is inferred from the account size; new fields
+    /// are zero-filled and the version byte (always last) is re-stamped. Fields
+    /// are only ever appended, so every older layout is a prefix of the current one
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        let target = ctx.accounts.target.to_account_info();
+
+        let layout_lens: &[usize] = {
+            let data = target.try_borrow_data()?;
+            require!(data.len() >= 8, ErrorCode::InvalidMigrationTarget);
+            if data[..8] == TreasureVault::DISCRIMINATOR {
+                &TreasureVault::LAYOUT_LENS
+            } else if data[..8] == BootyState::DISCRIMINATOR {
+                &BootyState::LAYOUT_LENS
+            } else {
+                return err!(ErrorCode::InvalidMigrationTarget);
+            }
+        };
+
+        // Layout lengths are indexed by version, so the size identifies the version
+        let old_len = target.data_len();
+        let version = layout_lens
+            .iter()
+            .position(|len| *len == old_len)
+            .ok_or(ErrorCode::InvalidMigrationTarget)?;
+        let current_version = layout_lens.len() - 1;
+        require!(version < current_version, ErrorCode::AlreadyMigrated);
+        let current_len = layout_lens[current_version];
+
+        grow_account(
+            &target,
+            current_len,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        // Version 0 had no version byte; later layouts end with one
+        let fields_end = if version == 0 { old_len } else { old_len - 1 };
+        let mut data = target.try_borrow_mut_data()?;
+        data[fields_end..current_len - 1].fill(0);
+        data[current_len - 1] = current_version as u8;
+
+        msg!("Migrated {} from version {} to {}", target.key(), version, current_version);
+
+        Ok(())
+    }
+
+    /// Admin function to upgrade a treasure record hidden before token_mint and
+    /// the later bookkeeping fields existed. The original layout doesn't say
+    /// which token was hidden, so the admin supplies the mint and the player's
+    /// token account for it; every other new field gets a fresh record's default
+    pub fn migrate_treasure_record(ctx: Context<MigrateTreasureRecord>) -> Result<()> {
+        let info = ctx.accounts.treasure_record.to_account_info();
+        let legacy = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() == TreasureRecord::LEGACY_LEN
+                    && data[..8] == TreasureRecord::DISCRIMINATOR,
+                ErrorCode::InvalidMigrationTarget
+            );
+            LegacyTreasureRecord::deserialize(&mut &data[8..])?
+        };
+
+        // Original records were PDAs of (player, treasure_id)
+        let expected = Pubkey::create_program_address(
+            &[
+                b"treasure",
+                legacy.player.as_ref(),
+                &legacy.timestamp.to_le_bytes(),
+                &[legacy.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidMigrationTarget)?;
+        require_keys_eq!(expected, info.key(), ErrorCode::InvalidMigrationTarget);
+        require_keys_eq!(
+            ctx.accounts.source_token_account.owner,
+            legacy.player,
+            ErrorCode::InvalidTokenAccount
+        );
+
+        let record = TreasureRecord {
+            player: legacy.player,
+            amount: legacy.amount,
+            timestamp: legacy.timestamp,
+            claimed: legacy.claimed,
+            tier: legacy.tier,
+            bump: legacy.bump,
+            token_mint: ctx.accounts.token_mint.key(),
+            claim_deadline: 0,
+            rewarded: false,
+            found_by: None,
+            created_ts: 0,
+            remaining: if legacy.claimed { 0 } else { legacy.amount },
+            vault_id: 0,
+            source_token_account: ctx.accounts.source_token_account.key(),
+            reclaimed: false,
+            reward_waived: false,
+            nft_minted: false,
+            note: String::new(),
+        };
+
+        grow_account(
+            &info,
+            TreasureRecord::LEN,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut data = info.try_borrow_mut_data()?;
+        record.try_serialize(&mut &mut data[..])?;
+
+        msg!("Migrated treasure record {} ({})", info.key(), record.token_mint);
+
+        Ok(())
+    }
+
+    /// Admin function to set the protocol fee taken from each treasure deposit
+    /// Capped at MAX_FEE_BPS (10%)
+    pub fn set_fee_bps(
+        ctx: Context<UpdateConfig>,
+        fee_bps: u16,
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, ErrorCode::FeeTooHigh);
+
+        let config = &mut ctx.accounts.config;
+        config.fee_bps = fee_bps;
+
+        msg!("Protocol fee set to {} bps", fee_bps);
+
+        Ok(())
+    }
+
+    /// Admin function to cap how much a single player can deposit over all time
+    /// Counts gross deposits (before fees); 0 removes the cap
+    pub fn set_max_player_deposit(
+        ctx: Context<UpdateConfig>,
+        max_player_deposit: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.max_player_deposit = max_player_deposit;
+
+        msg!("Max player deposit set to {}", max_player_deposit);
+
+        Ok(())
+    }
+
+    /// Admin function to pause or resume value-moving instructions
+    /// Emergency switch for when an exploit is discovered - admin and
+    /// read-only instructions stay callable so the team can recover
+    pub fn set_paused(
+        ctx: Context<UpdateConfig>,
+        paused: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.paused = paused;
+
+        msg!("Program paused: {}", paused);
+
+        Ok(())
+    }
+
+    // ====================================================================
+    // $BOOTY TOKEN SYSTEM
+    // ====================================================================
+
+    /// Initialize the $BOOTY token mint (one-time setup)
+    /// Creates a standard SPL token that the game program controls
+    pub fn initialize_booty_mint(
+        ctx: Context<InitializeBootyMint>,
+        decimals: u8,
+        max_supply: Option<u64>,
+    ) -> Result<()> {
+        require!(decimals <= MAX_BOOTY_DECIMALS, ErrorCode::InvalidDecimals);
+
+        let booty_state = &mut ctx.accounts.booty_state;
+        booty_state.set_inner(BootyState::new(
+            ctx.accounts.booty_mint.key(),
+            ctx.accounts.authority.key(),
+            ctx.bumps.booty_state,
+            decimals,
+            max_supply,
+        ));
+
+        msg!("$BOOTY token initialized!");
+        msg!("Mint: {}", booty_state.mint);
+        msg!("Authority: {}", booty_state.authority);
+        msg!("Decimals: {}", booty_state.decimals);
+        if let Some(max) = max_supply {
+            msg!("Max supply: {}", max);
+        } else {
+            msg!("Max supply: unlimited");
+        }
+
+        Ok(())
+    }
+
+    /// Mine (mint) $BOOTY tokens for a player
+    /// Called when a player buries treasure - rewards them with BOOTY based on hidden treasure amount
+    /// The BOOTY authority must co-sign, and once a BOOTY multisig is configured
+    /// its threshold of signers must approve as well
+    pub fn mine_booty(
+        ctx: Context<MineBooty>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        // Promotions scale the mined amount while their window is open
+        let amount = ctx
+            .accounts
+            .booty_state
+            .apply_bonus(amount, Clock::get()?.unix_timestamp)?;
+
+        // Referred players also mint a bonus to their referrer
+        let referral_bonus = match (ctx.accounts.player_profile.referrer, &ctx.accounts.referral_config) {
+            (Some(_), Some(config)) => calculate_fee(amount, config.bonus_bps)?,
+            _ => 0,
+        };
+        let total_minted = amount
+            .checked_add(referral_bonus)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // Enforce the max supply and mining rate limit
+        let rate_state = &mut ctx.accounts.mining_rate_state;
+        rate_state.bump = ctx.bumps.mining_rate_state;
+        check_mint_limits(&ctx.accounts.booty_state, rate_state, total_minted)?;
+
+        // The referral bonus isn't the player's own mining, so only `amount` counts
+        let daily_state = &mut ctx.accounts.daily_mine_state;
+        daily_state.bump = ctx.bumps.daily_mine_state;
+        check_daily_cap(&ctx.accounts.booty_state, daily_state, amount)?;
+
+        let mint_timestamp = &mut ctx.accounts.mint_timestamp;
+        mint_timestamp.last_mint_ts = Clock::get()?.unix_timestamp;
+        mint_timestamp.bump = ctx.bumps.mint_timestamp;
+
+        msg!("Mining {} BOOTY tokens for player {}", amount, ctx.accounts.player.key());
+
+        // Mint tokens using game program as authority
+        let bump = ctx.accounts.booty_state.bump;
+        let seeds = &[
+            b"booty-state".as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.booty_mint.to_account_info(),
+                    to: ctx.accounts.player_booty_account.to_account_info(),
+                    authority: ctx.accounts.booty_state.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        if referral_bonus > 0 {
+            let referrer_booty_account = ctx
+                .accounts
+                .referrer_booty_account
+                .as_ref()
+                .ok_or(ErrorCode::InvalidTokenAccount)?;
+
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::MintTo {
+                        mint: ctx.accounts.booty_mint.to_account_info(),
+                        to: referrer_booty_account.to_account_info(),
+                        authority: ctx.accounts.booty_state.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                referral_bonus,
+            )?;
+
+            msg!("Minted {} BOOTY referral bonus to {}", referral_bonus, referrer_booty_account.owner);
+        }
+
+        // Update total mined
+        let booty_state = &mut ctx.accounts.booty_state;
+        booty_state.total_mined = booty_state.total_mined
+            .checked_add(total_minted)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // Update player's lifetime stats
+        let profile = &mut ctx.accounts.player_profile;
+        profile.player = ctx.accounts.player.key();
+        profile.bump = ctx.bumps.player_profile;
+        profile.booty_mined = profile
+            .booty_mined
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Successfully mined {} BOOTY tokens", amount);
+        msg!("Total mined: {}", booty_state.total_mined);
+        msg!("Net supply: {}", net_supply(booty_state)?);
+
+        Ok(())
+    }
+
+    /// Mine the BOOTY reward for a hidden treasure, computed on-chain from its tier
+    /// reward = base_reward * tier multiplier (bps) from the RewardCurve; each
+    /// treasure can be rewarded once
+    pub fn mine_booty_for_treasure(ctx: Context<MineBootyForTreasure>) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+
+        let tier = ctx.accounts.treasure_record.tier;
+        let reward = ctx
+            .accounts
+            .reward_curve
+            .reward_for_tier(tier, ctx.accounts.booty_state.decimals)?;
+        let reward = ctx
+            .accounts
+            .booty_state
+            .apply_bonus(reward, Clock::get()?.unix_timestamp)?;
+
+        // Enforce the max supply and mining rate limit
+        let rate_state = &mut ctx.accounts.mining_rate_state;
+        rate_state.bump = ctx.bumps.mining_rate_state;
+        check_mint_limits(&ctx.accounts.booty_state, rate_state, reward)?;
+
+        let daily_state = &mut ctx.accounts.daily_mine_state;
+        daily_state.bump = ctx.bumps.daily_mine_state;
+        check_daily_cap(&ctx.accounts.booty_state, daily_state, reward)?;
+
+        let mint_timestamp = &mut ctx.accounts.mint_timestamp;
+        mint_timestamp.last_mint_ts = Clock::get()?.unix_timestamp;
+        mint_timestamp.bump = ctx.bumps.mint_timestamp;
+
+        msg!("Mining {} BOOTY for a tier {} treasure", reward, tier);
+
+        let bump = ctx.accounts.booty_state.bump;
+        let seeds = &[
+            b"booty-state".as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.booty_mint.to_account_info(),
+                    to: ctx.accounts.player_booty_account.to_account_info(),
+                    authority: ctx.accounts.booty_state.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            reward,
+        )?;
+
+        // Mark the treasure so the same hide can't be rewarded twice
+        ctx.accounts.treasure_record.rewarded = true;
+
+        let booty_state = &mut ctx.accounts.booty_state;
+        booty_state.total_mined = booty_state.total_mined
+            .checked_add(reward)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let profile = &mut ctx.accounts.player_profile;
+        profile.player = ctx.accounts.player.key();
+        profile.bump = ctx.bumps.player_profile;
+        profile.booty_mined = profile
+            .booty_mined
+            .checked_add(reward)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Total mined: {}", booty_state.total_mined);
+
+        Ok(())
+    }
+
+    /// Set the tier-based BOOTY reward curve (BOOTY authority only)
+    /// base_reward is in whole BOOTY; tier_multipliers_bps[i] scales it for tier i + 1
+    pub fn set_reward_curve(
+        ctx: Context<SetRewardCurve>,
+        base_reward: u64,
+        tier_multipliers_bps: [u16; 4],
+    ) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        let curve = &mut ctx.accounts.reward_curve;
+        curve.base_reward = base_reward;
+        curve.tier_multipliers_bps = tier_multipliers_bps;
+        curve.bump = ctx.bumps.reward_curve;
+
+        msg!("Reward curve: base {} BOOTY, multipliers {:?} bps", base_reward, tier_multipliers_bps);
+
+        Ok(())
+    }
+
+    /// Report whether `token_mint` can be hidden, without side effects
+    /// Clients simulate this before building a deposit and decode the returned
+    /// WhitelistStatus; a mint that was never whitelisted reads as not whitelisted
+    pub fn check_whitelist(ctx: Context<CheckWhitelist>, token_mint: Pubkey) -> Result<WhitelistStatus> {
+        let whitelist = &ctx.accounts.whitelist;
+        if !is_program_account(whitelist, &TokenWhitelist::DISCRIMINATOR) {
+            return Ok(WhitelistStatus {
+                whitelisted: false,
+                enabled: false,
+                min_deposit: 0,
+            });
+        }
+
+        let data = whitelist.try_borrow_data()?;
+        let entry = TokenWhitelist::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(entry.token_mint, token_mint, ErrorCode::MintMismatch);
+
+        Ok(WhitelistStatus {
+            whitelisted: true,
+            enabled: entry.enabled,
+            min_deposit: entry.min_deposit()?,
+        })
+    }
+
+    /// List the enabled whitelisted token mints without side effects
+    /// Clients simulate this and decode the returned mints instead of
+    /// scanning every whitelist PDA with getProgramAccounts
+    pub fn list_whitelisted_tokens(ctx: Context<ListWhitelistedTokens>) -> Result<Vec<Pubkey>> {
+        Ok(ctx.accounts.whitelist_index.mints.clone())
+    }
+
+    /// Sum the BOOTY a player could still mine for their treasures, without side effects
+    /// remaining_accounts holds the player's TreasureRecords; claimed, rewarded,
+    /// waived or reclaimed ones are skipped. Clients simulate this and decode
+    /// the returned RewardsPreview (the current mining bonus is included)
+    pub fn preview_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PreviewRewards<'info>>,
+        player: Pubkey,
+    ) -> Result<RewardsPreview> {
+        require!(
+            ctx.remaining_accounts.len() <= MAX_PREVIEW_RECORDS,
+            ErrorCode::BatchTooLarge
+        );
+
+        let curve = &ctx.accounts.reward_curve;
+        let decimals = ctx.accounts.booty_state.decimals;
+        let now = Clock::get()?.unix_timestamp;
+        let mut preview = RewardsPreview {
+            total_reward: 0,
+            records: 0,
+        };
+
+        for info in ctx.remaining_accounts.iter() {
+            let treasure_record = Account::<TreasureRecord>::try_from(info)?;
+            require_keys_eq!(treasure_record.player, player, ErrorCode::Unauthorized);
+            if treasure_record.claimed
+                || treasure_record.rewarded
+                || treasure_record.reward_waived
+                || treasure_record.reclaimed
+            {
+                continue;
+            }
+
+            let reward = ctx
+                .accounts
+                .booty_state
+                .apply_bonus(curve.reward_for_tier(treasure_record.tier, decimals)?, now)?;
+            preview.total_reward = preview
+                .total_reward
+                .checked_add(reward)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            preview.records += 1;
+        }
+
+        Ok(preview)
+    }
+
+    /// Read BOOTY supply figures without side effects
+    /// Clients simulate this and decode the returned SupplyInfo
+    pub fn read_supply(ctx: Context<ReadSupply>) -> Result<SupplyInfo> {
+        let booty_state = &ctx.accounts.booty_state;
+
+        Ok(SupplyInfo {
+            total_mined: booty_state.total_mined,
+            total_burned: booty_state.total_burned,
+            net_supply: net_supply(booty_state)?,
+            max_supply: booty_state.max_supply,
+        })
+    }
+
+    /// Burn $BOOTY tokens from a player
+    /// Called when a player moves their ship - consumes BOOTY as travel cost
+    pub fn burn_booty_for_travel(
+        ctx: Context<BurnBootyForTravel>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+
+        check_hold_time(&ctx.accounts.booty_state, &ctx.accounts.mint_timestamp)?;
+
+        let booty_state = &mut ctx.accounts.booty_state;
+
+        msg!("Burning {} BOOTY tokens from player {} for travel", amount, ctx.accounts.player.key());
+
+        // Burn tokens from player's account
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: ctx.accounts.booty_mint.to_account_info(),
+                    from: ctx.accounts.player_booty_account.to_account_info(),
+                    authority: ctx.accounts.player.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        // Update total burned
+        booty_state.total_burned = booty_state.total_burned
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Successfully burned {} BOOTY tokens", amount);
+        msg!("Total burned: {}", booty_state.total_burned);
+        msg!("Net supply: {}", net_supply(booty_state)?);
+
+        Ok(())
+    }
+
+    /// Admin function to freeze a player's BOOTY account (bans, anti-cheat)
+    /// The BOOTY state PDA is the mint's freeze authority, so it signs the CPI;
+    /// the balance stays intact and can be released with thaw_player_account
+    pub fn freeze_player_account(ctx: Context<FreezeBootyAccount>) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        let bump = ctx.accounts.booty_state.bump;
+        let seeds = &[
+            b"booty-state".as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::FreezeAccount {
+                account: ctx.accounts.player_booty_account.to_account_info(),
+                mint: ctx.accounts.booty_mint.to_account_info(),
+                authority: ctx.accounts.booty_state.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        emit!(AccountFrozen {
+            account: ctx.accounts.player_booty_account.key(),
+            owner: ctx.accounts.player_booty_account.owner,
+            authority: ctx.accounts.authority.key(),
+        });
+
+        msg!("Froze BOOTY account of {}", ctx.accounts.player_booty_account.owner);
+
+        Ok(())
+    }
+
+    /// Admin function to thaw a previously frozen player BOOTY account
+    pub fn thaw_player_account(ctx: Context<FreezeBootyAccount>) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        let bump = ctx.accounts.booty_state.bump;
+        let seeds = &[
+            b"booty-state".as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::ThawAccount {
+                account: ctx.accounts.player_booty_account.to_account_info(),
+                mint: ctx.accounts.booty_mint.to_account_info(),
+                authority: ctx.accounts.booty_state.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        emit!(AccountThawed {
+            account: ctx.accounts.player_booty_account.key(),
+            owner: ctx.accounts.player_booty_account.owner,
+            authority: ctx.accounts.authority.key(),
+        });
+
+        msg!("Thawed BOOTY account of {}", ctx.accounts.player_booty_account.owner);
+
+        Ok(())
+    }
+
+    /// Admin function to give the BOOTY mint fungible Metaplex metadata
+    /// (name/symbol/logo) so wallets display it. The BOOTY state PDA is the
+    /// mint authority, so it signs and becomes the update authority.
+    /// Calling it again once the metadata exists is a no-op
+    pub fn create_booty_metadata(
+        ctx: Context<CreateBootyMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        if !ctx.accounts.metadata.data_is_empty() {
+            msg!("BOOTY metadata already exists");
+            return Ok(());
+        }
+
+        validate_metadata(&name, &symbol, &uri)?;
+
+        let bump = ctx.accounts.booty_state.bump;
+        let seeds = &[
+            b"booty-state".as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let metadata_data = DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    payer: ctx.accounts.authority.to_account_info(),
+                    mint: ctx.accounts.booty_mint.to_account_info(),
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    mint_authority: ctx.accounts.booty_state.to_account_info(),
+                    update_authority: ctx.accounts.booty_state.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            metadata_data,
+            true, // is_mutable (logo/URI may change later)
+            true, // update_authority_is_signer
+            None, // collection_details
+        )?;
+
+        msg!("BOOTY metadata created for mint {}", ctx.accounts.booty_mint.key());
+
+        Ok(())
+    }
+
+    // ====================================================================
+    // BOOTY STAKING
+    // ====================================================================
+
+    /// Admin function to configure BOOTY staking rewards
+    /// apr_bps is the yearly reward rate, lockup_seconds the minimum stake time
+    pub fn set_staking_params(
+        ctx: Context<UpdateBootyState>,
+        apr_bps: u16,
+        lockup_seconds: i64,
+    ) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        require!(lockup_seconds >= 0, ErrorCode::InvalidStakingParams);
+
+        let booty_state = &mut ctx.accounts.booty_state;
+        booty_state.stake_apr_bps = apr_bps;
+        booty_state.stake_lockup_seconds = lockup_seconds;
+
+        msg!("Staking APR: {} bps", apr_bps);
+        msg!("Staking lockup: {} seconds", lockup_seconds);
+
+        Ok(())
+    }
+
+    /// Raise (or remove) the BOOTY max supply (BOOTY authority only)
+    /// The cap can only increase, never decrease
+    pub fn update_booty_max_supply(
+        ctx: Context<UpdateBootyState>,
+        new_max: Option<u64>,
+    ) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        let booty_state = &mut ctx.accounts.booty_state;
+
+        if let Some(new_max) = new_max {
+            // Going from unlimited to a cap, or lowering a cap, is a decrease
+            let current_max = booty_state.max_supply.ok_or(ErrorCode::CannotDecreaseMaxSupply)?;
+            require!(new_max >= current_max, ErrorCode::CannotDecreaseMaxSupply);
+            require!(new_max >= booty_state.total_mined, ErrorCode::MaxBelowMinted);
+        }
+
+        booty_state.max_supply = new_max;
+
+        msg!("BOOTY max supply updated to {:?}", new_max);
+
+        Ok(())
+    }
+
+    /// Set the share of mined BOOTY minted as a bonus to a player's referrer
+    /// (BOOTY authority only)
+    pub fn set_referral_bonus(ctx: Context<SetReferralBonus>, bonus_bps: u16) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        require!(bonus_bps <= MAX_REFERRAL_BPS, ErrorCode::ReferralBonusTooHigh);
+
+        let config = &mut ctx.accounts.referral_config;
+        config.bonus_bps = bonus_bps;
+        config.bump = ctx.bumps.referral_config;
+
+        msg!("Referral bonus: {} bps", bonus_bps);
+
+        Ok(())
+    }
+
+    /// Configure the m-of-n signer set guarding BOOTY admin instructions
+    /// Once set, mining and every BOOTY admin instruction (this one included)
+    /// also need `threshold` of the listed authorities as signers in remaining_accounts
+    pub fn set_booty_multisig(
+        ctx: Context<SetBootyMultisig>,
+        authorities: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let multisig = &mut ctx.accounts.booty_multisig;
+
+        // An existing signer set must approve its own replacement
+        if multisig.threshold > 0 {
+            multisig.require_approval(ctx.remaining_accounts)?;
+        }
+
+        require!(
+            !authorities.is_empty() && authorities.len() <= MAX_MULTISIG_AUTHORITIES,
+            ErrorCode::InvalidMultisig
+        );
+        require!(
+            threshold > 0 && threshold as usize <= authorities.len(),
+            ErrorCode::InvalidMultisig
+        );
+        for (i, authority) in authorities.iter().enumerate() {
+            require!(!authorities[..i].contains(authority), ErrorCode::InvalidMultisig);
+        }
+
+        multisig.authorities = authorities;
+        multisig.threshold = threshold;
+        multisig.bump = ctx.bumps.booty_multisig;
+
+        msg!(
+            "BOOTY multisig set: {} of {} authorities",
+            threshold,
+            multisig.authorities.len()
+        );
+
+        Ok(())
+    }
+
+    /// Set the mining rate limit (BOOTY authority only)
+    /// A max_per_window of 0 disables the limit
+    pub fn set_mining_rate_limit(
+        ctx: Context<UpdateBootyState>,
+        max_per_window: u64,
+        window_seconds: i64,
+    ) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        require!(
+            max_per_window == 0 || window_seconds > 0,
+            ErrorCode::InvalidRateLimit
+        );
+
+        let booty_state = &mut ctx.accounts.booty_state;
+        booty_state.max_per_window = max_per_window;
+        booty_state.window_seconds = window_seconds;
+
+        msg!("Mining limit: {} BOOTY per {} seconds", max_per_window, window_seconds);
+
+        Ok(())
+    }
+
+    /// Admin function to cap how much BOOTY each player can mine per UTC day
+    /// 0 removes the cap; subject to the BOOTY multisig when one is configured
+    pub fn set_daily_mine_cap(
+        ctx: Context<UpdateBootyState>,
+        daily_mine_cap: u64,
+    ) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        let booty_state = &mut ctx.accounts.booty_state;
+        booty_state.daily_mine_cap = daily_mine_cap;
+
+        msg!("Daily mining cap: {} BOOTY per player", daily_mine_cap);
+
+        Ok(())
+    }
+
+    /// Admin function to require BOOTY be held for a while before it is
+    /// burned for travel, discouraging mint-and-burn cycling (0 = no wait)
+    /// Subject to the BOOTY multisig when one is configured
+    pub fn set_min_hold_seconds(
+        ctx: Context<UpdateBootyState>,
+        min_hold_seconds: i64,
+    ) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        require!(min_hold_seconds >= 0, ErrorCode::InvalidHoldTime);
+
+        let booty_state = &mut ctx.accounts.booty_state;
+        booty_state.min_hold_seconds = min_hold_seconds;
+
+        msg!("Minimum BOOTY hold time: {} seconds", min_hold_seconds);
+
+        Ok(())
+    }
+
+    /// Admin function to schedule a mining promotion (e.g. double BOOTY weekends)
+    /// Mining in [bonus_start, bonus_end) is scaled by bonus_multiplier_bps;
+    /// subject to the BOOTY multisig when one is configured
+    pub fn set_bonus_window(
+        ctx: Context<UpdateBootyState>,
+        bonus_multiplier_bps: u16,
+        bonus_start: i64,
+        bonus_end: i64,
+    ) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        require!(bonus_start < bonus_end, ErrorCode::InvalidBonusWindow);
+        require!(
+            bonus_multiplier_bps as u64 >= BPS_DENOMINATOR
+                && bonus_multiplier_bps <= MAX_BONUS_MULTIPLIER_BPS,
+            ErrorCode::InvalidBonusWindow
+        );
+
+        let booty_state = &mut ctx.accounts.booty_state;
+        booty_state.bonus_multiplier_bps = bonus_multiplier_bps;
+        booty_state.bonus_start = bonus_start;
+        booty_state.bonus_end = bonus_end;
+
+        msg!(
+            "Mining bonus {} bps from {} to {}",
+            bonus_multiplier_bps,
+            bonus_start,
+            bonus_end
+        );
+
+        Ok(())
+    }
+
+    /// Stake BOOTY tokens to earn time-based rewards
+    /// Tokens move into the stake vault (owned by the BOOTY state PDA)
+    pub fn stake_booty(
+        ctx: Context<StakeBooty>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+
+        msg!("Player {} staking {} BOOTY", ctx.accounts.player.key(), amount);
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.player_booty_account.to_account_info(),
+                    mint: ctx.accounts.booty_mint.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.player.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.booty_mint.decimals,
+        )?;
+
+        let stake_record = &mut ctx.accounts.stake_record;
+        stake_record.player = ctx.accounts.player.key();
+        stake_record.amount = amount;
+        stake_record.start_ts = Clock::get()?.unix_timestamp;
+        stake_record.bump = ctx.bumps.stake_record;
+
+        msg!("Stake recorded at {}", stake_record.start_ts);
+
+        Ok(())
+    }
+
+    /// Unstake BOOTY: returns the principal plus rewards minted for the time staked
+    /// Rewards are capped at the remaining max supply so principal is never locked
+    pub fn unstake_booty(ctx: Context<UnstakeBooty>) -> Result<()> {
+        let stake_record = &ctx.accounts.stake_record;
+        let booty_state = &ctx.accounts.booty_state;
+
+        let elapsed = Clock::get()?
+            .unix_timestamp
+            .checked_sub(stake_record.start_ts)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            elapsed >= booty_state.stake_lockup_seconds,
+            ErrorCode::StakeNotMatured
+        );
+
+        let mut reward = calculate_stake_reward(
+            stake_record.amount,
+            booty_state.stake_apr_bps,
+            elapsed,
+        )?;
+        if let Some(max_supply) = booty_state.max_supply {
+            let remaining = max_supply.saturating_sub(booty_state.total_mined);
+            reward = reward.min(remaining);
+        }
+
+        let principal = stake_record.amount;
+
+        msg!("Unstaking {} BOOTY after {} seconds", principal, elapsed);
+
+        let bump = booty_state.bump;
+        let seeds = &[
+            b"booty-state".as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Return principal from the stake vault
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    mint: ctx.accounts.booty_mint.to_account_info(),
+                    to: ctx.accounts.player_booty_account.to_account_info(),
+                    authority: ctx.accounts.booty_state.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            principal,
+            ctx.accounts.booty_mint.decimals,
+        )?;
+
+        // Mint staking rewards
+        if reward > 0 {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::MintTo {
+                        mint: ctx.accounts.booty_mint.to_account_info(),
+                        to: ctx.accounts.player_booty_account.to_account_info(),
+                        authority: ctx.accounts.booty_state.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                reward,
+            )?;
+
+            let booty_state = &mut ctx.accounts.booty_state;
+            booty_state.total_mined = booty_state
+                .total_mined
+                .checked_add(reward)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+
+        msg!("Returned {} BOOTY principal and {} BOOTY rewards", principal, reward);
+
+        Ok(())
+    }
+
+    // ====================================================================
+    // BOOTY VESTING
+    // ====================================================================
+
+    /// Create a linear BOOTY vesting schedule for a team/treasury wallet
+    /// (BOOTY authority only). Nothing is minted until the beneficiary claims
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        total: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        duration: i64,
+    ) -> Result<()> {
+        check_booty_multisig(&ctx.accounts.booty_multisig, ctx.remaining_accounts)?;
+
+        let end_ts = start_ts
+            .checked_add(duration)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            total > 0 && duration > 0 && cliff_ts >= start_ts && cliff_ts <= end_ts,
+            ErrorCode::InvalidVestingSchedule
+        );
+
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.beneficiary = ctx.accounts.beneficiary.key();
+        schedule.total = total;
+        schedule.claimed = 0;
+        schedule.start_ts = start_ts;
+        schedule.cliff_ts = cliff_ts;
+        schedule.duration = duration;
+        schedule.bump = ctx.bumps.vesting_schedule;
+
+        msg!("Vesting {} BOOTY to {} over {} seconds", total, schedule.beneficiary, duration);
+
+        Ok(())
+    }
+
+    /// Mint the BOOTY that has vested since the beneficiary's last claim
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &ctx.accounts.vesting_schedule;
+        require!(now >= schedule.cliff_ts, ErrorCode::CliffNotReached);
+
+        let vested = calculate_vested(schedule.total, schedule.start_ts, schedule.duration, now)?;
+        let amount = vested
+            .checked_sub(schedule.claimed)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(amount > 0, ErrorCode::NothingVested);
+
+        // Check max supply if set
+        if let Some(max_supply) = ctx.accounts.booty_state.max_supply {
+            let new_total = ctx.accounts.booty_state.total_mined
+                .checked_add(amount)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            require!(new_total <= max_supply, ErrorCode::MaxSupplyExceeded);
+        }
+
+        msg!("Claiming {} vested BOOTY for {}", amount, schedule.beneficiary);
+
+        let bump = ctx.accounts.booty_state.bump;
+        let seeds = &[
+            b"booty-state".as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.booty_mint.to_account_info(),
+                    to: ctx.accounts.beneficiary_booty_account.to_account_info(),
+                    authority: ctx.accounts.booty_state.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.claimed = vested;
+
+        let booty_state = &mut ctx.accounts.booty_state;
+        booty_state.total_mined = booty_state.total_mined
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Vested so far: {} of {}", schedule.claimed, schedule.total);
+
+        Ok(())
+    }
+
+    // ====================================================================
+    // PLAYER TRADING
+    // ====================================================================
+
+    /// Offer an asset (an NFT or BOOTY) in exchange for another asset
+    /// The offered tokens are locked in an escrow account owned by the offer PDA
+    pub fn create_trade_offer(
+        ctx: Context<CreateTradeOffer>,
+        trade_id: u64,
+        offer_amount: u64,
+        request_mint: Pubkey,
+        request_amount: u64,
+    ) -> Result<()> {
+        require!(offer_amount > 0 && request_amount > 0, ErrorCode::InvalidTradeAmount);
+
+        msg!("Player {} offering {} of {}", ctx.accounts.maker.key(), offer_amount, ctx.accounts.offer_mint.key());
+        msg!("Requesting {} of {}", request_amount, request_mint);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.maker_offer_account.to_account_info(),
+                    to: ctx.accounts.escrow_token_account.to_account_info(),
+                    authority: ctx.accounts.maker.to_account_info(),
+                },
+            ),
+            offer_amount,
+        )?;
+
+        let trade_offer = &mut ctx.accounts.trade_offer;
+        trade_offer.maker = ctx.accounts.maker.key();
+        trade_offer.trade_id = trade_id;
+        trade_offer.offer_mint = ctx.accounts.offer_mint.key();
+        trade_offer.offer_amount = offer_amount;
+        trade_offer.request_mint = request_mint;
+        trade_offer.request_amount = request_amount;
+        trade_offer.bump = ctx.bumps.trade_offer;
+
+        Ok(())
+    }
+
+    /// Accept a trade offer: the taker pays the requested asset to the maker
+    /// and receives the escrowed asset in the same transaction
+    pub fn accept_trade(ctx: Context<AcceptTrade>) -> Result<()> {
+        let trade_offer = &ctx.accounts.trade_offer;
+
+        msg!("Player {} accepting trade {} from {}", ctx.accounts.taker.key(), trade_offer.trade_id, trade_offer.maker);
+
+        // Taker pays the maker
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.taker_request_account.to_account_info(),
+                    to: ctx.accounts.maker_request_account.to_account_info(),
+                    authority: ctx.accounts.taker.to_account_info(),
+                },
+            ),
+            trade_offer.request_amount,
+        )?;
+
+        // Escrow pays the taker, then the emptied escrow is closed
+        release_trade_escrow(
+            &ctx.accounts.trade_offer,
+            ctx.accounts.escrow_token_account.to_account_info(),
+            ctx.accounts.taker_offer_account.to_account_info(),
+            ctx.accounts.maker.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        msg!("Trade completed");
+
+        Ok(())
+    }
+
+    /// Cancel a trade offer and return the escrowed asset to the maker
+    pub fn cancel_trade(ctx: Context<CancelTrade>) -> Result<()> {
+        msg!("Player {} cancelling trade {}", ctx.accounts.maker.key(), ctx.accounts.trade_offer.trade_id);
+
+        release_trade_escrow(
+            &ctx.accounts.trade_offer,
+            ctx.accounts.escrow_token_account.to_account_info(),
+            ctx.accounts.maker_offer_account.to_account_info(),
+            ctx.accounts.maker.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        msg!("Trade cancelled");
+
+        Ok(())
+    }
+}
+
+// ====================================================================
+// HELPER FUNCTIONS
+// ====================================================================
+
+/// Build the Metaplex creators list from instruction arguments
+/// Returns None for an empty list, otherwise shares must total 100
+/// The update authority is marked verified because it signs the mint
+fn build_creators(args: &[CreatorArg], update_authority: Pubkey) -> Result<Option<Vec<Creator>>> {
+    if args.is_empty() {
+        return Ok(None);
+    }
+
+    require!(args.len() <= MAX_CREATORS, ErrorCode::InvalidCreatorShares);
+
+    let total_share = args
+        .iter()
+        .try_fold(0u8, |total, creator| total.checked_add(creator.share))
+        .ok_or(ErrorCode::InvalidCreatorShares)?;
+    require!(total_share == 100, ErrorCode::InvalidCreatorShares);
+
+    let creators = args
+        .iter()
+        .map(|creator| Creator {
+            address: creator.address,
+            verified: creator.address == update_authority,
+            share: creator.share,
+        })
+        .collect();
+
+    Ok(Some(creators))
+}
+
+/// Move a trade's escrowed asset to `to` (signed by the offer PDA) and close
+/// the escrow token account, refunding its rent to `rent_receiver`
+fn release_trade_escrow<'info>(
+    trade_offer: &Account<'info, TradeOffer>,
+    escrow_token_account: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    rent_receiver: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let trade_id_bytes = trade_offer.trade_id.to_le_bytes();
+    let seeds = &[
+        b"trade".as_ref(),
+        trade_offer.maker.as_ref(),
+        trade_id_bytes.as_ref(),
+        &[trade_offer.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            Transfer {
+                from: escrow_token_account.clone(),
+                to,
+                authority: trade_offer.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        trade_offer.offer_amount,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program,
+        token::CloseAccount {
+            account: escrow_token_account,
+            destination: rent_receiver,
+            authority: trade_offer.to_account_info(),
+        },
+        signer_seeds,
+    ))
+}
+
+/// Check that `player` holds the NFT described by `metadata` and that it is a
+/// genuine game collectible (minted by an authorized minter) with `expected_symbol`
+fn require_collectible_ownership(
+    token_account: &TokenAccount,
+    metadata: &MetadataAccount,
+    minter_registry: &MintAuthorityRegistry,
+    player: Pubkey,
+    expected_symbol: &str,
+) -> Result<()> {
+    require!(
+        token_account.owner == player
+            && token_account.amount == 1
+            && token_account.mint == metadata.mint
+            && minter_registry.minters.contains(&metadata.update_authority)
+            && metadata.symbol.trim_end_matches('\0') == expected_symbol,
+        ErrorCode::CollectibleNotHeld
+    );
+    Ok(())
+}
+
+/// Split a monster symbol into its family and tier (e.g. "KRAKEN2" -> ("KRAKEN", 2))
+fn parse_monster_symbol(symbol: &str) -> Result<(&str, u8)> {
+    let tier = symbol
+        .chars()
+        .last()
+        .and_then(|c| c.to_digit(10))
+        .ok_or(ErrorCode::MismatchedInputs)? as u8;
+    let family = &symbol[..symbol.len() - 1]; // ASCII digit is a single byte
+    require!(!family.is_empty() && tier > 0, ErrorCode::MismatchedInputs);
+
+    Ok((family, tier))
+}
+
+/// Record a player's new running total on the leaderboard
+/// Keeps entries sorted by total_hidden (highest first) and capped at
+/// LEADERBOARD_SIZE; returns the player's 0-based rank if the board changed
+fn update_leaderboard(entries: &mut Vec<LeaderboardEntry>, player: Pubkey, total_hidden: u64) -> Option<u8> {
+    let previous = entries.clone();
+
+    entries.retain(|entry| entry.player != player);
+    let rank = entries
+        .iter()
+        .position(|entry| entry.total_hidden < total_hidden)
+        .unwrap_or(entries.len());
+
+    if rank < LEADERBOARD_SIZE {
+        entries.insert(rank, LeaderboardEntry { player, total_hidden });
+        entries.truncate(LEADERBOARD_SIZE);
+    }
+
+    if *entries == previous || rank >= LEADERBOARD_SIZE {
+        return None;
+    }
+
+    Some(rank as u8)
+}
+
+/// Verify a Merkle proof for `leaf` at position `index` (keccak256 tree)
+/// Each index bit picks which side the running hash sits on, which binds
+/// the proof to a single leaf position
+fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32], index: u32) -> bool {
+    let mut computed = leaf;
+    let mut position = index;
+
+    for sibling in proof {
+        computed = if position & 1 == 0 {
+            keccak::hashv(&[&computed, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &computed]).to_bytes()
+        };
+        position >>= 1;
+    }
+
+    position == 0 && computed == root
+}
+
+/// Seed component for PDAs scoped to a vault. The default vault 0 uses an
+/// empty seed so its addresses match those created before vault ids existed
+fn vault_id_seed(vault_id: u64) -> Vec<u8> {
+    if vault_id == 0 {
+        Vec::new()
+    } else {
+        vault_id.to_le_bytes().to_vec()
+    }
+}
+
+/// Check metadata strings against Metaplex's limits before any CPI, so
+/// clients get a clear error instead of an opaque Metaplex failure
+fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(name.len() <= MAX_NAME_LENGTH, ErrorCode::NameTooLong);
+    require!(symbol.len() <= MAX_SYMBOL_LENGTH, ErrorCode::SymbolTooLong);
+    require!(!uri.is_empty(), ErrorCode::EmptyUri);
+    require!(uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+
+    Ok(())
+}
+
+/// Calculate tier based on treasure amount (in base units of a token with `decimals`)
+/// Returns tier 1-4, higher tier = more tokens hidden
+fn calculate_tier(amount: u64, decimals: u8) -> Result<u8> {
+    let tokens = amount / token_unit(decimals)?; // Convert from base units to whole tokens
+
+    let tier = if tokens >= 100_000 {
+        4 // Legendary tier
+    } else if tokens >= 10_000 {
+        3 // Epic tier
+    } else if tokens >= 1_000 {
+        2 // Rare tier
+    } else {
+        1 // Common tier
+    };
+
+    Ok(tier)
+}
+
+/// Calculate tier based on the USD value of the treasure (whole dollars)
+/// Returns tier 1-4 using the same shape as the token-count tiers
+fn calculate_usd_tier(value_usd: u64) -> u8 {
+    if value_usd >= TIER_LEGENDARY_USD {
+        4 // Legendary tier
+    } else if value_usd >= TIER_EPIC_USD {
+        3 // Epic tier
+    } else if value_usd >= TIER_RARE_USD {
+        2 // Rare tier
+    } else {
+        1 // Common tier
+    }
+}
+
+/// Read the aggregate price from a Pyth price account, rejecting prices that
+/// aren't trading, aren't positive, or are older than `max_age` seconds
+/// Offsets follow pyth-sdk-solana's PriceAccount layout; the crate itself pins
+/// solana-program <= 1.16, which conflicts with the version Anchor 0.30 uses
+fn read_pyth_price(price_feed: &AccountInfo, max_age: u64) -> Result<(i64, i32)> {
+    let data = price_feed.try_borrow_data()?;
+    require!(data.len() >= PYTH_AGG_STATUS_OFFSET + 4, ErrorCode::InvalidPriceFeed);
+
+    let read_u32 = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    let read_i64 = |offset: usize| i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+    require!(read_u32(PYTH_MAGIC_OFFSET) == PYTH_MAGIC, ErrorCode::InvalidPriceFeed);
+    require!(
+        read_u32(PYTH_AGG_STATUS_OFFSET) == PYTH_STATUS_TRADING,
+        ErrorCode::InvalidPriceFeed
+    );
+
+    let expo = read_u32(PYTH_EXPO_OFFSET) as i32;
+    let publish_time = read_i64(PYTH_TIMESTAMP_OFFSET);
+    let price = read_i64(PYTH_AGG_PRICE_OFFSET);
+    require!(price > 0, ErrorCode::InvalidPriceFeed);
+
+    let age = Clock::get()?.unix_timestamp.saturating_sub(publish_time);
+    require!(age <= max_age as i64, ErrorCode::StalePriceFeed);
+
+    Ok((price, expo))
+}
+
+/// Value of `amount` base units (token has `decimals`) in whole US dollars,
+/// given a Pyth price of `price * 10^expo` dollars per whole token
+fn usd_value(amount: u64, decimals: u8, price: i64, expo: i32) -> Result<u64> {
+    let mut numerator = (amount as u128)
+        .checked_mul(price as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let mut denominator_exp = decimals as u32;
+
+    if expo >= 0 {
+        numerator = numerator
+            .checked_mul(10u128.checked_pow(expo as u32).ok_or(ErrorCode::ArithmeticOverflow)?)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    } else {
+        denominator_exp = denominator_exp
+            .checked_add(expo.unsigned_abs())
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+
+    let denominator = 10u128
+        .checked_pow(denominator_exp)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    u64::try_from(numerator / denominator).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// Staking reward for `amount` staked `elapsed` seconds at `apr_bps` per year
+fn calculate_stake_reward(amount: u64, apr_bps: u16, elapsed: i64) -> Result<u64> {
+    let elapsed = u128::try_from(elapsed).map_err(|_| error!(ErrorCode::ArithmeticOverflow))?;
+
+    let reward = (amount as u128)
+        .checked_mul(apr_bps as u128)
+        .and_then(|value| value.checked_mul(elapsed))
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / (BPS_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128);
+
+    u64::try_from(reward).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// Amount of a linear vesting schedule released by `now`
+fn calculate_vested(total: u64, start_ts: i64, duration: i64, now: i64) -> Result<u64> {
+    let elapsed = now.saturating_sub(start_ts).clamp(0, duration);
+
+    let vested = (total as u128)
+        .checked_mul(elapsed as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / duration as u128;
+
+    u64::try_from(vested).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// BOOTY in circulation (mined minus burned)
+/// Burned can never exceed mined; if it does the supply accounting is broken
+fn net_supply(booty_state: &BootyState) -> Result<u64> {
+    booty_state
+        .total_mined
+        .checked_sub(booty_state.total_burned)
+        .ok_or(error!(ErrorCode::SupplyAccountingError))
+}
+
+/// Protocol fee owed on a deposit of `amount` at `fee_bps` basis points
+fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / BPS_DENOMINATOR as u128;
+
+    Ok(fee as u64)
+}
+
+/// Check a new BOOTY mint against the max supply and the mining rate window
+/// (rate limit disabled when max_per_window is 0), recording it in the window
+fn check_mint_limits(
+    booty_state: &BootyState,
+    rate_state: &mut MiningRateState,
+    amount: u64,
+) -> Result<()> {
+    // Check max supply if set
+    if let Some(max_supply) = booty_state.max_supply {
+        let new_total = booty_state.total_mined
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            new_total <= max_supply,
+            ErrorCode::MaxSupplyExceeded
+        );
+    }
+
+    // Start a fresh window once the previous one has expired
+    let now = Clock::get()?.unix_timestamp;
+    if now.saturating_sub(rate_state.window_start) >= booty_state.window_seconds {
+        rate_state.window_start = now;
+        rate_state.minted_in_window = 0;
+    }
+
+    let minted_in_window = rate_state.minted_in_window
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if booty_state.max_per_window > 0 {
+        require!(
+            minted_in_window <= booty_state.max_per_window,
+            ErrorCode::MiningRateExceeded
+        );
+    }
+    rate_state.minted_in_window = minted_in_window;
+
+    Ok(())
+}
+
+/// Enforce the per-player daily mining cap, counting `amount` toward today
+/// Days are UTC days (unix_timestamp / SECONDS_PER_DAY); a new day resets the count
+fn check_daily_cap(
+    booty_state: &BootyState,
+    daily_state: &mut DailyMineState,
+    amount: u64,
+) -> Result<()> {
+    let today = Clock::get()?.unix_timestamp / SECONDS_PER_DAY;
+    if daily_state.day != today {
+        daily_state.day = today;
+        daily_state.minted_today = 0;
+    }
+
+    let minted_today = daily_state.minted_today
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if booty_state.daily_mine_cap > 0 {
+        require!(
+            minted_today <= booty_state.daily_mine_cap,
+            ErrorCode::DailyCapExceeded
+        );
+    }
+    daily_state.minted_today = minted_today;
+
+    Ok(())
+}
+
+/// Map a mint's requested freeze authority to whether it must be revoked
+/// - None: the freeze authority is revoked before the master edition, so the
+///   NFT is freely transferable and can never be frozen
+/// - Some(payer): the master edition takes the freeze authority over, so the
+///   NFT stays potentially freezable through Metaplex delegate freezing
+///   (staking, soulbound flows)
+///
+/// Metaplex only hands over a freeze authority that matches the mint
+/// authority, so the payer is the only key accepted
+fn revokes_freeze_authority(freeze_authority: Option<Pubkey>, payer: Pubkey) -> Result<bool> {
+    match freeze_authority {
+        None => Ok(true),
+        Some(authority) => {
+            require_keys_eq!(authority, payer, ErrorCode::InvalidFreezeAuthority);
+            Ok(false)
+        }
+    }
+}
+
+/// Enforce the NFT symbol/name policy when one is configured and enabled
+/// The PDA's address is fixed by seeds, so a missing account means open minting
+fn check_nft_policy(policy: &AccountInfo, title: &str, symbol: &str) -> Result<()> {
+    if !is_program_account(policy, &NftPolicy::DISCRIMINATOR) {
+        return Ok(());
+    }
+
+    let data = policy.try_borrow_data()?;
+    let policy = NftPolicy::try_deserialize(&mut &data[..])?;
+    if !policy.enabled {
+        return Ok(());
+    }
+
+    require!(symbol == policy.symbol, ErrorCode::SymbolNotAllowed);
+    if let Some(prefix) = &policy.name_prefix {
+        require!(title.starts_with(prefix.as_str()), ErrorCode::NamePrefixRequired);
+    }
+
+    Ok(())
+}
+
+/// Seller fee and creators for a new NFT
+/// A RoyaltyConfig, when it exists, sets the seller fee for every mint. The
+/// caller's creators are kept but must include its recipient so royalties
+/// reach it; without creators the recipient is the sole (100%) creator.
+/// Without a RoyaltyConfig NFTs carry no royalty
+fn royalty_terms(
+    royalty_config: &AccountInfo,
+    creators: Option<Vec<Creator>>,
+    payer: Pubkey,
+) -> Result<(u16, Option<Vec<Creator>>)> {
+    if !is_program_account(royalty_config, &RoyaltyConfig::DISCRIMINATOR) {
+        return Ok((0, creators));
+    }
+
+    let data = royalty_config.try_borrow_data()?;
+    let config = RoyaltyConfig::try_deserialize(&mut &data[..])?;
+    let creators = match creators {
+        Some(creators) => {
+            require!(
+                creators.iter().any(|creator| creator.address == config.recipient),
+                ErrorCode::RoyaltyRecipientMissing
+            );
+            creators
+        }
+        None => vec![Creator {
+            address: config.recipient,
+            verified: config.recipient == payer,
+            share: 100,
+        }],
+    };
+
+    Ok((config.seller_fee_basis_points, Some(creators)))
+}
+
+/// Enforce the BOOTY multisig when one has been configured
+/// The PDA's address is fixed by seeds, so a missing account means single-key mode
+fn check_booty_multisig(multisig: &AccountInfo, signers: &[AccountInfo]) -> Result<()> {
+    if !is_program_account(multisig, &BootyMultisig::DISCRIMINATOR) {
+        return Ok(());
+    }
+
+    let data = multisig.try_borrow_data()?;
+    BootyMultisig::try_deserialize(&mut &data[..])?.require_approval(signers)
+}
+
+/// Require the player's last mine to be at least min_hold_seconds old
+/// Token balances carry no timestamps, so the player's latest mine stands in
+/// for the age of their BOOTY; a player who never mined has nothing to wait on
+fn check_hold_time(booty_state: &BootyState, mint_timestamp: &AccountInfo) -> Result<()> {
+    if booty_state.min_hold_seconds == 0
+        || !is_program_account(mint_timestamp, &MintTimestamp::DISCRIMINATOR)
+    {
+        return Ok(());
+    }
+
+    let data = mint_timestamp.try_borrow_data()?;
+    let last_mint_ts = MintTimestamp::try_deserialize(&mut &data[..])?.last_mint_ts;
+    let held_for = Clock::get()?.unix_timestamp.saturating_sub(last_mint_ts);
+    require!(held_for >= booty_state.min_hold_seconds, ErrorCode::HoldTimeNotMet);
+
+    Ok(())
+}
+
+/// Grow a program account to `new_len`, first topping its rent up from `payer`
+fn grow_account<'info>(
+    target: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required.saturating_sub(target.lamports());
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                SystemTransfer {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    target.realloc(new_len, false)?;
+    Ok(())
+}
+
+/// Whether an account exists, is owned by this program and holds the given type
+fn is_program_account(account: &AccountInfo, discriminator: &[u8]) -> bool {
+    account.owner == &crate::ID
+        && account
+            .try_borrow_data()
+            .map(|data| data.len() >= 8 && &data[..8] == discriminator)
+            .unwrap_or(false)
+}
+
+/// Once a reward curve exists every treasure earns BOOTY, so claiming waits
+/// until that reward has been mined (or waived by the player)
+fn reward_settled(reward_curve: &AccountInfo, treasure_record: &TreasureRecord) -> bool {
+    treasure_record.rewarded
+        || treasure_record.reward_waived
+        || !is_program_account(reward_curve, &RewardCurve::DISCRIMINATOR)
+}
+
+/// Fail with RewardPending unless the treasure's reward is settled
+fn check_reward_settled(reward_curve: &AccountInfo, treasure_record: &TreasureRecord) -> Result<()> {
+    require!(reward_settled(reward_curve, treasure_record), ErrorCode::RewardPending);
+    Ok(())
+}
+
+/// Scale an amount by a basis-point multiplier (may exceed 100%)
+fn apply_bps(amount: u64, bps: u16) -> Result<u64> {
+    let scaled = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / BPS_DENOMINATOR as u128;
+
+    u64::try_from(scaled).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// Number of base units in one whole token (10^decimals)
+fn token_unit(decimals: u8) -> Result<u64> {
+    10u64
+        .checked_pow(decimals as u32)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// Base units of `whole` BOOTY at the mint's `decimals`
+/// BOOTY-denominated parameters are stored in whole tokens and scaled here
+/// at read time, so they stay correct whatever decimals the mint uses
+fn booty_amount(whole: u64, decimals: u8) -> Result<u64> {
+    whole
+        .checked_mul(token_unit(decimals)?)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+}
+
+// ====================================================================
+// INSTRUCTION ARGUMENTS
+// ====================================================================
+
+/// Minimum treasure that can be hidden, in whole tokens
+pub const MIN_TREASURE_TOKENS: u64 = 100;
+
+/// Basis points denominator (100% = 10,000 bps)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Maximum protocol fee on deposits (10%)
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// Largest per-token tier multiplier (2x the deposit's value)
+pub const MAX_TIER_MULTIPLIER_BPS: u16 = 20_000;
+
+/// Seconds in a (365-day) year, used for staking APR
+pub const SECONDS_PER_YEAR: i64 = 365 * 24 * 60 * 60;
+
+/// Seconds in a day, used for the daily mining cap
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// USD value (whole dollars) needed for each tier when a price feed is set
+pub const TIER_RARE_USD: u64 = 10;
+pub const TIER_EPIC_USD: u64 = 100;
+pub const TIER_LEGENDARY_USD: u64 = 1_000;
+
+/// Pyth price account layout (see read_pyth_price)
+pub const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+pub const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_MAGIC_OFFSET: usize = 0;
+const PYTH_EXPO_OFFSET: usize = 20;
+const PYTH_TIMESTAMP_OFFSET: usize = 96;
+const PYTH_AGG_PRICE_OFFSET: usize = 208;
+const PYTH_AGG_STATUS_OFFSET: usize = 224;
+
+/// Metaplex metadata string limits (bytes)
+pub const MAX_NAME_LENGTH: usize = 32;
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_URI_LENGTH: usize = 200;
+
+/// Metadata symbol of soulbound deposit receipts
+pub const RECEIPT_SYMBOL: &str = "RCPT";
+
+/// Maximum number of creators Metaplex allows on a single metadata account
+pub const MAX_CREATORS: usize = 5;
+
+/// Most decimals initialize_booty_mint accepts (the practical SPL maximum)
+pub const MAX_BOOTY_DECIMALS: u8 = 9;
+
+/// Wait between propose_drain and execute_drain (48 hours)
+pub const DRAIN_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+
+/// Most TreasureRecords preview_rewards reads in one call
+pub const MAX_PREVIEW_RECORDS: usize = 20;
+
+/// Size of each ClaimedNonces bitmap shard
+pub const NONCE_SHARD_BYTES: usize = 64;
+
+/// treasure_ids covered by one ClaimedNonces shard
+pub const NONCE_SHARD_IDS: i64 = NONCE_SHARD_BYTES as i64 * 8;
+
+/// Largest mining bonus set_bonus_window accepts (3x)
+pub const MAX_BONUS_MULTIPLIER_BPS: u16 = 30_000;
+
+/// Number of remaining_accounts each NFT takes up in mint_nft_batch
+pub const NFT_BATCH_ACCOUNTS: usize = 4;
+
+/// Largest batch mint_nft_batch accepts (bounded by compute and tx size)
+pub const MAX_NFT_BATCH_SIZE: usize = 5;
+
+/// Largest batch claim_treasure_batch accepts (one record load and write per entry)
+pub const MAX_CLAIM_BATCH_SIZE: usize = 10;
+
+/// Reason codes returned by check_claimable
+pub const CLAIM_STATUS_CLAIMABLE: u8 = 0;
+pub const CLAIM_STATUS_ALREADY_CLAIMED: u8 = 1;
+pub const CLAIM_STATUS_EXPIRED: u8 = 2;
+pub const CLAIM_STATUS_PAUSED: u8 = 3;
+pub const CLAIM_STATUS_TOO_EARLY: u8 = 4;
+pub const CLAIM_STATUS_RECLAIMED: u8 = 5;
+pub const CLAIM_STATUS_REWARD_PENDING: u8 = 6;
+
+/// Number of remaining_accounts each mint takes up in whitelist_tokens_batch
+pub const WHITELIST_BATCH_ACCOUNTS: usize = 2;
+
+/// Largest batch whitelist_tokens_batch accepts (one PDA creation per mint)
+pub const MAX_WHITELIST_BATCH_SIZE: usize = 10;
+
+/// Maximum mints in the whitelist index (4 + 32 * 31 bytes fits the 1024-byte return data limit)
+pub const MAX_WHITELIST_INDEX_SIZE: usize = 31;
+
+/// Fee for each searched coordinate, in whole BOOTY (scaled by booty_amount)
+pub const SEARCH_FEE_TOKENS: u64 = 1;
+
+/// Largest batch search_treasure_batch accepts (one PDA creation per coordinate)
+pub const MAX_SEARCH_BATCH_SIZE: usize = 10;
+
+/// BOOTY minted to a player when the authority verifies their find, in whole BOOTY
+pub const FIND_REWARD_TOKENS: u64 = 10;
+
+/// Highest monster tier (tiers are a single digit in the metadata symbol)
+pub const MAX_MONSTER_TIER: u8 = 9;
+
+/// Number of tiers TierSupply tracks; higher tiers count against the last one
+pub const NFT_TIER_COUNT: usize = 4;
+
+/// Number of players tracked on the leaderboard
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// Longest note annotate_treasure accepts (bytes)
+pub const MAX_NOTE_LEN: usize = 64;
+
+/// Highest referral bonus allowed (20% of the mined amount)
+pub const MAX_REFERRAL_BPS: u16 = 2_000;
+
+/// Most keys the BOOTY multisig can hold
+pub const MAX_MULTISIG_AUTHORITIES: usize = 10;
+
+/// Most backend wallets the minter registry can hold
+pub const MAX_MINTERS: usize = 10;
+
+/// Size of each allowlist claim bitmap account
+pub const ALLOWLIST_BITMAP_BYTES: usize = 256;
+
+/// Allowlist leaves tracked by one claim bitmap account
+pub const ALLOWLIST_LEAVES_PER_BITMAP: u32 = (ALLOWLIST_BITMAP_BYTES * 8) as u32;
+
+/// Creator entry passed to mint_nft (share is a percentage of royalties)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreatorArg {
+    pub address: Pubkey,   // Creator wallet
+    pub share: u8,         // Percentage share (all shares must total 100)
+}
+
+/// Map coordinate passed to search_treasure_batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SearchCoord {
+    pub x: i32, // X coordinate to search
+    pub y: i32, // Y coordinate to search
+}
+
+/// Leaderboard slot: a player and their lifetime hidden total
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,     // Player's wallet
+    pub total_hidden: u64,  // Lifetime tokens hidden
+}
+
+/// Claim eligibility returned by check_claimable
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimableStatus {
+    pub claimable: bool, // claim_treasure would succeed now
+    pub reason: u8,      // One of the CLAIM_STATUS_* codes
+}
+
+/// Deposit eligibility of a mint returned by check_whitelist
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WhitelistStatus {
+    pub whitelisted: bool, // A whitelist entry exists for the mint
+    pub enabled: bool,     // The entry currently accepts deposits
+    pub min_deposit: u64,  // Smallest accepted deposit in base units (0 if not whitelisted)
+}
+
+/// Unmined BOOTY rewards returned by preview_rewards
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RewardsPreview {
+    pub total_reward: u64, // BOOTY the counted records would mint right now
+    pub records: u32,      // Records still owed a reward
+}
+
+/// Setup progress returned by is_initialized
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializationStatus {
+    pub vault: bool,       // initialize_vault has run
+    pub booty_state: bool, // initialize_booty_mint has run
+}
+
+/// BOOTY supply snapshot returned by read_supply
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SupplyInfo {
+    pub total_mined: u64,
+    pub total_burned: u64,
+    pub net_supply: u64,
+    pub max_supply: Option<u64>,
+}
+
+/// Per-NFT arguments for mint_nft_batch (mirrors mint_nft's arguments)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct NftMintArgs {
+    pub metadata_title: String,
+    pub metadata_symbol: String,
+    pub metadata_uri: String,
+    pub creators: Vec<CreatorArg>,
+    pub is_mutable: bool,
+    pub freeze_authority: Option<Pubkey>, // Freeze mode, see revokes_freeze_authority
+}
+
+// ====================================================================
+// ACCOUNT STRUCTURES
+// ====================================================================
+
+/// Backend wallets allowed to mint NFTs
+#[account]
+pub struct MintAuthorityRegistry {
+    pub minters: Vec<Pubkey>, // Authorized minting wallets (4 + 32 * MAX_MINTERS bytes)
+    pub bump: u8,             // PDA bump (1 byte)
+}
+
+impl MintAuthorityRegistry {
+    pub const LEN: usize = 8 + 4 + 32 * MAX_MINTERS + 1; // discriminator + fields
+}
+
+/// Enabled whitelisted mints, so clients can list them in one read
+#[account]
+pub struct WhitelistIndex {
+    pub mints: Vec<Pubkey>, // Enabled whitelisted mints (4 + 32 * MAX_WHITELIST_INDEX_SIZE bytes)
+    pub bump: u8,           // PDA bump (1 byte)
+}
+
+impl WhitelistIndex {
+    pub const LEN: usize = 8 + 4 + 32 * MAX_WHITELIST_INDEX_SIZE + 1; // discriminator + fields
+
+    /// Add `mint` if it isn't listed yet
+    pub fn insert(&mut self, mint: Pubkey) -> Result<()> {
+        if !self.mints.contains(&mint) {
+            require!(self.mints.len() < MAX_WHITELIST_INDEX_SIZE, ErrorCode::WhitelistFull);
+            self.mints.push(mint);
+        }
+        Ok(())
+    }
+
+    /// Drop `mint` if it is listed
+    pub fn remove(&mut self, mint: &Pubkey) {
+        if let Some(index) = self.mints.iter().position(|m| m == mint) {
+            self.mints.swap_remove(index);
+        }
+    }
+}
+
+/// m-of-n signer set required for sensitive BOOTY admin instructions
+#[account]
+pub struct BootyMultisig {
+    pub authorities: Vec<Pubkey>, // Keys allowed to approve (4 + 32 * MAX_MULTISIG_AUTHORITIES bytes)
+    pub threshold: u8,            // Distinct approvals required (1 byte)
+    pub bump: u8,                 // PDA bump (1 byte)
+}
+
+impl BootyMultisig {
+    pub const LEN: usize = 8 + 4 + 32 * MAX_MULTISIG_AUTHORITIES + 1 + 1; // discriminator + fields
+
+    /// Require `threshold` distinct authorities among the signers
+    pub fn require_approval(&self, signers: &[AccountInfo]) -> Result<()> {
+        let mut approvals: Vec<Pubkey> = Vec::with_capacity(self.authorities.len());
+        for signer in signers.iter().filter(|account| account.is_signer) {
+            if self.authorities.contains(signer.key) && !approvals.contains(signer.key) {
+                approvals.push(*signer.key);
+            }
+        }
+
+        require!(
+            approvals.len() >= self.threshold as usize,
+            ErrorCode::ThresholdNotMet
+        );
+
+        Ok(())
+    }
+}
+
+/// Symbol and name rules every minted NFT must follow while enabled
+#[account]
+pub struct NftPolicy {
+    pub enabled: bool,               // Enforce the policy? (1 byte)
+    pub symbol: String,              // Required metadata symbol (4 + MAX_SYMBOL_LENGTH bytes)
+    pub name_prefix: Option<String>, // Required title prefix (1 + 4 + MAX_NAME_LENGTH bytes)
+    pub bump: u8,                    // PDA bump (1 byte)
+}
+
+impl NftPolicy {
+    pub const LEN: usize = 8 + 1 + 4 + MAX_SYMBOL_LENGTH + 1 + 4 + MAX_NAME_LENGTH + 1; // discriminator + fields
+}
+
+/// Royalty terms stamped on every minted NFT
+#[account]
+pub struct RoyaltyConfig {
+    pub recipient: Pubkey,            // Creator every NFT must pay royalties to (32 bytes)
+    pub seller_fee_basis_points: u16, // Royalty rate (2 bytes)
+    pub bump: u8,                     // PDA bump (1 byte)
+}
+
+impl RoyaltyConfig {
+    pub const LEN: usize = 8 + 32 + 2 + 1; // discriminator + fields
+}
+
+/// Merkle root of the wallets allowed to mint the current drop
+#[account]
+pub struct MerkleAllowlist {
+    pub root: [u8; 32], // keccak256 Merkle root of player wallets (32 bytes)
+    pub bump: u8,       // PDA bump (1 byte)
+}
+
+impl MerkleAllowlist {
+    pub const LEN: usize = 8 + 32 + 1; // discriminator + fields
+}
+
+/// Claimed bitmap for a range of allowlist leaves (one bit per leaf)
+#[account]
+pub struct AllowlistClaims {
+    pub bits: [u8; ALLOWLIST_BITMAP_BYTES], // Claimed flags (256 bytes)
+    pub bump: u8,                           // PDA bump (1 byte)
+}
+
+impl AllowlistClaims {
+    pub const LEN: usize = 8 + ALLOWLIST_BITMAP_BYTES + 1; // discriminator + fields
+}
+
+/// Main vault account holding treasure accounting (admin settings live in ProgramConfig)
+#[account]
+pub struct TreasureVault {
+    pub authority: Pubkey,    // Admin at creation; the live admin is ProgramConfig.authority (32 bytes)
+    pub total_hidden: u64,    // Total tokens hidden (8 bytes)
+    pub total_claimed: u64,   // Total treasures claimed (8 bytes)
+    pub bump: u8,             // PDA bump (1 byte)
+    pub season: u32,          // Current season number (4 bytes)
+    pub archived_hidden: u64, // Tokens hidden in earlier seasons still held (8 bytes)
+    pub total_fees_collected: u64, // Protocol fees and forfeits paid into fee accounts (8 bytes)
+    pub total_fees_withdrawn: u64, // Amount withdrawn from fee accounts (8 bytes)
+    pub total_burned_treasure: u64, // Deposited tokens destroyed by burn_vault_tokens (8 bytes)
+    pub vault_id: u64,        // Distinguishes vaults sharing one deployment, 0 = default (8 bytes)
+    pub version: u8,          // Account layout version, always last (1 byte)
+}
+
+impl TreasureVault {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1 + 4 + 8 + 8 + 8 + 8 + 8 + 1; // discriminator + fields
+    pub const VERSION: u8 = 1;
+
+    /// Account size of each layout version (index = version)
+    /// Version 0 is the original four-field vault, deployed before versioning
+    pub const LAYOUT_LENS: [usize; 2] = [8 + 32 + 8 + 8 + 1, Self::LEN];
+
+    /// Empty vault at the current layout version
+    pub fn new(authority: Pubkey, bump: u8, vault_id: u64) -> Self {
+        Self {
+            authority,
+            total_hidden: 0,
+            total_claimed: 0,
+            bump,
+            season: 0,
+            archived_hidden: 0,
+            total_fees_collected: 0,
+            total_fees_withdrawn: 0,
+            total_burned_treasure: 0,
+            vault_id,
+            version: Self::VERSION,
+        }
+    }
+
+    /// Seed component scoping PDAs to this vault, see vault_id_seed
+    pub fn id_seed(&self) -> Vec<u8> {
+        vault_id_seed(self.vault_id)
+    }
+
+    /// Publish the current totals so indexers can track vault metrics from logs
+    pub fn emit_stats(&self) {
+        emit!(VaultStatsChanged {
+            vault_id: self.vault_id,
+            total_hidden: self.total_hidden,
+            total_claimed: self.total_claimed,
+        });
+    }
+
+    /// Remove `amount` from the tokens the vault owes players, drawing on
+    /// the current season first and then on earlier seasons
+    pub fn debit_hidden(&mut self, amount: u64) -> Result<()> {
+        let from_current = amount.min(self.total_hidden);
+        self.archived_hidden = self
+            .archived_hidden
+            .checked_sub(amount - from_current)
+            .ok_or(ErrorCode::InsufficientVaultBalance)?;
+        self.total_hidden -= from_current;
+
+        Ok(())
+    }
+}
+
+/// Global admin settings, shared by every admin-gated instruction
+#[account]
+pub struct ProgramConfig {
+    pub authority: Pubkey,    // Admin who can update settings (32 bytes)
+    pub pending_authority: Option<Pubkey>, // Proposed new admin awaiting acceptance (1 + 32 bytes)
+    pub paused: bool,         // Emergency pause for value-moving instructions (1 byte)
+    pub fee_bps: u16,         // Protocol fee on deposits in basis points (2 bytes)
+    pub max_player_deposit: u64, // Lifetime deposit cap per player, 0 = unlimited (8 bytes)
+    pub claim_window: i64,    // Seconds a treasure stays claimable, 0 = forever (8 bytes)
+    pub claim_delay_seconds: i64, // Minimum time between hiding and claiming (8 bytes)
+    pub require_monotonic_ids: bool, // Each player's search/treasure ids must increase (1 byte)
+    pub bump: u8,             // PDA bump (1 byte)
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = 8 + 32 + 33 + 1 + 2 + 8 + 8 + 8 + 1 + 1; // discriminator + fields
+
+    /// Default settings for a fresh deployment
+    pub fn new(authority: Pubkey, bump: u8) -> Self {
+        Self {
+            authority,
+            pending_authority: None,
+            paused: false,
+            fee_bps: 0,
+            max_player_deposit: 0,
+            claim_window: 0,
+            claim_delay_seconds: 0,
+            require_monotonic_ids: false,
+            bump,
+        }
+    }
+}
+
+/// Player treasure record (one per player per hidden treasure)
+#[account]
+pub struct TreasureRecord {
+    pub player: Pubkey,    // Player's wallet (32 bytes)
+    pub amount: u64,       // Amount hidden (8 bytes)
+    pub timestamp: i64,    // Caller-supplied treasure_id, only a PDA nonce (8 bytes)
+    pub claimed: bool,     // Has treasure been claimed? (1 byte)
+    pub tier: u8,          // Tier earned (1-4) (1 byte)
+    pub bump: u8,          // PDA bump (1 byte)
+    pub token_mint: Pubkey, // Mint of the hidden tokens (32 bytes)
+    pub claim_deadline: i64, // Last second the treasure can be claimed, 0 = never expires (8 bytes)
+    pub rewarded: bool,    // Has BOOTY been mined for this treasure? (1 byte)
+    pub found_by: Option<Pubkey>, // First player verified as finding it (1 + 32 bytes)
+    pub created_ts: i64,   // On-chain clock time when hidden (8 bytes)
+    pub remaining: u64,    // Amount not yet claimed, starts at amount (8 bytes)
+    pub vault_id: u64,     // Vault the tokens were hidden in (8 bytes)
+    pub source_token_account: Pubkey, // Token account the deposit came from, the only reclaim destination (32 bytes)
+    pub reclaimed: bool,   // Has the player taken any of it back? Reclaimed treasure can't be claimed (1 byte)
+    pub reward_waived: bool, // Did the player give up the BOOTY reward? Settles it without minting (1 byte)
+    pub nft_minted: bool,  // Has its premium NFT been minted via mint_nft? (1 byte)
+    pub note: String,      // Optional player note, grown by annotate_treasure (4 + up to MAX_NOTE_LEN bytes)
+}
+
+impl TreasureRecord {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1 + 1 + 1 + 32 + 8 + 1 + 33 + 8 + 8 + 8 + 32 + 1 + 1 + 1 + 4; // discriminator + fields (empty note)
+
+    /// Size of records hidden before token_mint and the later fields existed
+    pub const LEGACY_LEN: usize = 8 + 32 + 8 + 8 + 1 + 1 + 1;
+
+    /// Whether the claim deadline has passed at `now`
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.claim_deadline != 0 && now > self.claim_deadline
+    }
+
+    /// Take `claim_amount` off the remaining balance, marking the treasure
+    /// claimed once nothing is left
+    pub fn withdraw(&mut self, claim_amount: u64) -> Result<()> {
+        require!(
+            claim_amount > 0 && claim_amount <= self.remaining,
+            ErrorCode::ClaimExceedsRemaining
+        );
+        self.remaining -= claim_amount;
+        if self.remaining == 0 {
+            self.claimed = true;
+        }
+        Ok(())
+    }
+
+    /// Account size with a note of `note_len` bytes
+    pub fn space_with_note(note_len: usize) -> usize {
+        Self::LEN + note_len
+    }
+}
+
+/// TreasureRecord fields as originally laid out, read by migrate_treasure_record
+#[derive(AnchorDeserialize)]
+struct LegacyTreasureRecord {
+    player: Pubkey,
+    amount: u64,
+    timestamp: i64,
+    claimed: bool,
+    tier: u8,
+    bump: u8,
+}
+
+/// Player search record (one per player per search attempt)
+#[account]
+pub struct SearchRecord {
+    pub player: Pubkey,    // Player's wallet (32 bytes)
+    pub x: i32,            // X coordinate searched (4 bytes)
+    pub y: i32,            // Y coordinate searched (4 bytes)
+    pub timestamp: i64,    // Caller-supplied search_id, only a PDA nonce (8 bytes)
+    pub found: bool,       // Was treasure found? (1 byte)
+    pub bump: u8,          // PDA bump (1 byte)
+    pub created_ts: i64,   // On-chain clock time of the search (8 bytes)
+}
+
+impl SearchRecord {
+    pub const LEN: usize = 8 + 32 + 4 + 4 + 8 + 1 + 1 + 8; // discriminator + fields
+}
+
+/// Token whitelist entry (which tokens can be hidden as treasure)
+#[account]
+pub struct TokenWhitelist {
+    pub token_mint: Pubkey,   // Token mint address (32 bytes)
+    pub enabled: bool,        // Is this token enabled? (1 byte)
+    pub bump: u8,             // PDA bump (1 byte)
+    pub decimals: u8,         // Token decimals, used to normalize amounts (1 byte)
+    pub price_feed: Option<Pubkey>, // Pyth price feed for USD tiers (1 + 32 bytes)
+    pub max_price_age: u64,   // Max price feed age in seconds (8 bytes)
+    pub min_deposit_override: Option<u64>, // Per-token minimum deposit in base units (1 + 8 bytes)
+    pub tier_multiplier: u16, // Tier scaling in bps, 10,000 = neutral (2 bytes)
+}
+
+impl TokenWhitelist {
+    pub const LEN: usize = 8 + 32 + 1 + 1 + 1 + 33 + 8 + 9 + 2; // discriminator + fields
+
+    /// Size of entries created before decimals and per-token economics existed
+    pub const LEGACY_LEN: usize = 8 + 32 + 1 + 1;
+
+    /// Read an entry in either the legacy or the current layout
+    /// Legacy entries come back with a new entry's defaults and zero decimals
+    pub fn read_any_layout(info: &AccountInfo) -> Result<Self> {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == Self::DISCRIMINATOR,
+            ErrorCode::InvalidMigrationTarget
+        );
+        if data.len() == Self::LEGACY_LEN {
+            let legacy = LegacyTokenWhitelist::deserialize(&mut &data[8..])?;
+            Ok(Self {
+                enabled: legacy.enabled,
+                ..Self::new(legacy.token_mint, 0, legacy.bump)
+            })
+        } else {
+            Self::try_deserialize(&mut &data[..])
+        }
+    }
+
+    /// A freshly whitelisted, enabled token with default economics
+    pub fn new(token_mint: Pubkey, decimals: u8, bump: u8) -> Self {
+        Self {
+            token_mint,
+            enabled: true,
+            bump,
+            decimals,
+            price_feed: None,
+            max_price_age: 0,
+            min_deposit_override: None,
+            tier_multiplier: BPS_DENOMINATOR as u16,
+        }
+    }
+
+    /// Smallest deposit accepted, in base units: the token's override, or
+    /// MIN_TREASURE_TOKENS whole tokens at the token's decimals
+    pub fn min_deposit(&self) -> Result<u64> {
+        match self.min_deposit_override {
+            Some(min_amount) => Ok(min_amount),
+            None => MIN_TREASURE_TOKENS
+                .checked_mul(token_unit(self.decimals)?)
+                .ok_or(error!(ErrorCode::ArithmeticOverflow)),
+        }
+    }
+}
+
+/// TokenWhitelist fields as originally laid out, read by read_any_layout
+#[derive(AnchorDeserialize)]
+struct LegacyTokenWhitelist {
+    token_mint: Pubkey,
+    enabled: bool,
+    bump: u8,
+}
+
+/// Player profile aggregating lifetime stats (one per player)
+/// Lets clients show a dashboard without scanning every record
+#[account]
+pub struct PlayerProfile {
+    pub player: Pubkey,            // Player's wallet (32 bytes)
+    pub total_hidden: u64,         // Lifetime tokens hidden (8 bytes)
+    pub treasures_claimed: u64,    // Lifetime treasures claimed (8 bytes)
+    pub searches_made: u64,        // Lifetime searches (8 bytes)
+    pub booty_mined: u64,          // Lifetime BOOTY mined (8 bytes)
+    pub highest_tier: u8,          // Highest tier ever earned (1 byte)
+    pub bump: u8,                  // PDA bump (1 byte)
+    pub referrer: Option<Pubkey>,  // Player who referred this one, fixed once set (1 + 32 bytes)
+    pub total_deposited: u64,      // Lifetime gross deposits, checked against the cap (8 bytes)
+    pub last_search_id: i64,       // Highest search_id used so far (8 bytes)
+    pub last_treasure_id: i64,     // Highest treasure_id used so far (8 bytes)
+}
+
+impl PlayerProfile {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 33 + 8 + 8 + 8; // discriminator + fields
+
+    /// Record a new search_id, requiring it to exceed the last when `enforce` is set
+    pub fn record_search_id(&mut self, search_id: i64, enforce: bool) -> Result<()> {
+        Self::advance_id(&mut self.last_search_id, search_id, enforce)
+    }
+
+    /// Record a new treasure_id, requiring it to exceed the last when `enforce` is set
+    pub fn record_treasure_id(&mut self, treasure_id: i64, enforce: bool) -> Result<()> {
+        Self::advance_id(&mut self.last_treasure_id, treasure_id, enforce)
+    }
+
+    // The highest id is tracked even while unenforced, so turning the check
+    // on later still orders ids against everything the player already used
+    fn advance_id(last: &mut i64, id: i64, enforce: bool) -> Result<()> {
+        if enforce {
+            require!(id > *last, ErrorCode::NonMonotonicId);
+        }
+        *last = (*last).max(id);
+        Ok(())
+    }
+}
+
+/// Tier-based BOOTY reward curve used by mine_booty_for_treasure
+#[account]
+pub struct RewardCurve {
+    pub base_reward: u64,               // Whole BOOTY reward before the tier multiplier (8 bytes)
+    pub tier_multipliers_bps: [u16; 4], // Multiplier per tier 1-4 in bps (8 bytes)
+    pub bump: u8,                       // PDA bump (1 byte)
+}
+
+impl RewardCurve {
+    pub const LEN: usize = 8 + 8 + 8 + 1; // discriminator + fields
+
+    /// BOOTY reward in base units for a treasure of `tier` (1-4), before any
+    /// mining bonus; `decimals` are the BOOTY mint's
+    pub fn reward_for_tier(&self, tier: u8, decimals: u8) -> Result<u64> {
+        let multiplier = *self
+            .tier_multipliers_bps
+            .get((tier as usize).saturating_sub(1))
+            .ok_or(ErrorCode::InvalidRewardCurve)?;
+        let reward = apply_bps(booty_amount(self.base_reward, decimals)?, multiplier)?;
+        require!(reward > 0, ErrorCode::InvalidRewardCurve);
+        Ok(reward)
+    }
+}
+
+/// Referral bonus configuration
+#[account]
+pub struct ReferralConfig {
+    pub bonus_bps: u16, // Share of mined BOOTY minted to the referrer (2 bytes)
+    pub bump: u8,       // PDA bump (1 byte)
+}
+
+impl ReferralConfig {
+    pub const LEN: usize = 8 + 2 + 1; // discriminator + fields
+}
+
+/// Top treasure hiders, sorted by lifetime total (highest first)
+#[account]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>, // Ranked entries (4 + 40 * LEADERBOARD_SIZE bytes)
+    pub bump: u8,                       // PDA bump (1 byte)
+}
+
+impl Leaderboard {
+    pub const LEN: usize = 8 + 4 + (32 + 8) * LEADERBOARD_SIZE + 1; // discriminator + fields
+}
+
+/// BOOTY token state tracking
+#[account]
+pub struct BootyState {
+    pub mint: Pubkey,              // The BOOTY token mint address (32 bytes)
+    pub authority: Pubkey,          // Authority that manages the token (32 bytes)
+    pub total_mined: u64,          // Total tokens mined/minted (8 bytes)
+    pub total_burned: u64,         // Total tokens burned (8 bytes)
+    pub max_supply: Option<u64>,   // Optional maximum supply (1 + 8 bytes)
+    pub bump: u8,                  // PDA bump (1 byte)
+    pub stake_apr_bps: u16,        // Yearly staking reward rate (2 bytes)
+    pub stake_lockup_seconds: i64, // Minimum stake duration (8 bytes)
+    pub max_per_window: u64,       // Max BOOTY mined per window, 0 = unlimited (8 bytes)
+    pub window_seconds: i64,       // Length of the mining rate window (8 bytes)
+    pub daily_mine_cap: u64,       // Max BOOTY each player mines per UTC day, 0 = unlimited (8 bytes)
+    pub min_hold_seconds: i64,     // Wait after a player's last mine before burning, 0 = none (8 bytes)
+    pub decimals: u8,              // BOOTY mint decimals, see refresh_booty_decimals for migrated states (1 byte)
+    pub bonus_multiplier_bps: u16, // Mining multiplier during the bonus window, 0 = none (2 bytes)
+    pub bonus_start: i64,          // First second of the bonus window (8 bytes)
+    pub bonus_end: i64,            // Bonus window ends before this second (8 bytes)
+    pub version: u8,               // Account layout version, always last (1 byte)
+}
+
+impl BootyState {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1 + 8 + 1 + 2 + 8 + 8 + 8 + 8 + 8 + 1 + 2 + 8 + 8 + 1; // discriminator + fields
+    pub const VERSION: u8 = 1;
+
+    /// Account size of each layout version (index = version)
+    /// Version 0 is the original supply-only state, deployed before versioning
+    pub const LAYOUT_LENS: [usize; 2] = [8 + 32 + 32 + 8 + 8 + 1 + 8 + 1, Self::LEN];
+
+    /// Fresh BOOTY state with nothing mined and every limit switched off
+    pub fn new(
+        mint: Pubkey,
+        authority: Pubkey,
+        bump: u8,
+        decimals: u8,
+        max_supply: Option<u64>,
+    ) -> Self {
+        Self {
+            mint,
+            authority,
+            total_mined: 0,
+            total_burned: 0,
+            max_supply,
+            bump,
+            stake_apr_bps: 0,
+            stake_lockup_seconds: 0,
+            max_per_window: 0,
+            window_seconds: 0,
+            daily_mine_cap: 0,
+            min_hold_seconds: 0,
+            decimals,
+            bonus_multiplier_bps: 0,
+            bonus_start: 0,
+            bonus_end: 0,
+            version: Self::VERSION,
+        }
+    }
+
+    /// Scale a mined amount by the bonus multiplier while `now` is inside the
+    /// bonus window; outside it (or with no promotion set) the amount is unchanged
+    pub fn apply_bonus(&self, amount: u64, now: i64) -> Result<u64> {
+        if self.bonus_multiplier_bps == 0 || now < self.bonus_start || now >= self.bonus_end {
+            return Ok(amount);
+        }
+        apply_bps(amount, self.bonus_multiplier_bps)
+    }
+}
+
+/// Bitmap of treasure_ids a player has used, sharded by NONCE_SHARD_IDS so
+/// each account stays small (one per player per shard)
+#[account]
+pub struct ClaimedNonces {
+    pub player: Pubkey,                  // Owner of the treasure_ids (32 bytes)
+    pub shard: i64,                      // treasure_id / NONCE_SHARD_IDS, rounded down (8 bytes)
+    pub bits: [u8; NONCE_SHARD_BYTES],   // One bit per treasure_id in the shard (64 bytes)
+    pub bump: u8,                        // PDA bump (1 byte)
+}
+
+impl ClaimedNonces {
+    pub const LEN: usize = 8 + 32 + 8 + NONCE_SHARD_BYTES + 1; // discriminator + fields
+
+    /// Shard holding `treasure_id`
+    pub fn shard_of(treasure_id: i64) -> i64 {
+        treasure_id.div_euclid(NONCE_SHARD_IDS)
+    }
+
+    /// Record `treasure_id` as used, failing if it already was
+    pub fn mark(&mut self, treasure_id: i64) -> Result<()> {
+        let bit = treasure_id.rem_euclid(NONCE_SHARD_IDS) as usize;
+        let mask = 1u8 << (bit % 8);
+        require!(self.bits[bit / 8] & mask == 0, ErrorCode::TreasureIdReused);
+        self.bits[bit / 8] |= mask;
+        Ok(())
+    }
+}
+
+/// Emergency drain waiting out its timelock (one per vault token account)
+#[account]
+pub struct DrainProposal {
+    pub vault_token_account: Pubkey, // Vault token account to be drained (32 bytes)
+    pub destination: Pubkey,         // Token account receiving the balance (32 bytes)
+    pub unlock_ts: i64,              // Earliest time execute_drain succeeds (8 bytes)
+    pub bump: u8,                    // PDA bump (1 byte)
+}
+
+impl DrainProposal {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1; // discriminator + fields
+}
+
+/// Items minted into a collection and its cap (one per collection mint)
+#[account]
+pub struct CollectionState {
+    pub collection_mint: Pubkey, // Collection NFT mint (32 bytes)
+    pub minted: u64,             // Items minted via mint_collectible_in_collection (8 bytes)
+    pub max_supply: u64,         // Most items allowed, 0 = unlimited (8 bytes)
+    pub bump: u8,                // PDA bump (1 byte)
+}
+
+impl CollectionState {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1; // discriminator + fields
+
+    /// Count one more item, failing once max_supply is reached
+    pub fn record_mint(&mut self) -> Result<()> {
+        require!(
+            self.max_supply == 0 || self.minted < self.max_supply,
+            ErrorCode::CollectionSoldOut
+        );
+        self.minted = self
+            .minted
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// NFTs minted per tier and the most each tier may ever mint (singleton)
+#[account]
+pub struct TierSupply {
+    pub minted: [u64; NFT_TIER_COUNT], // Tiered NFTs minted so far, index = tier - 1 (32 bytes)
+    pub caps: [u64; NFT_TIER_COUNT],   // Most NFTs per tier, 0 = uncapped (32 bytes)
+    pub bump: u8,                      // PDA bump (1 byte)
+}
+
+impl TierSupply {
+    pub const LEN: usize = 8 + 8 * NFT_TIER_COUNT + 8 * NFT_TIER_COUNT + 1; // discriminator + fields
+
+    /// Count one more NFT of `tier`, failing once that tier's cap is reached
+    pub fn record_mint(&mut self, tier: u8) -> Result<()> {
+        let index = (tier.max(1) as usize - 1).min(NFT_TIER_COUNT - 1);
+        let cap = self.caps[index];
+        require!(cap == 0 || self.minted[index] < cap, ErrorCode::TierSoldOut);
+        self.minted[index] = self.minted[index]
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// When a player last mined BOOTY (one per player)
+#[account]
+pub struct MintTimestamp {
+    pub last_mint_ts: i64,       // On-chain clock time of the player's last mine (8 bytes)
+    pub bump: u8,                // PDA bump (1 byte)
+}
+
+impl MintTimestamp {
+    pub const LEN: usize = 8 + 8 + 1; // discriminator + fields
+}
+
+/// Per-player daily mining tally (one per player)
+#[account]
+pub struct DailyMineState {
+    pub day: i64,                // UTC day of the tally, unix_timestamp / SECONDS_PER_DAY (8 bytes)
+    pub minted_today: u64,       // BOOTY mined by the player on that day (8 bytes)
+    pub bump: u8,                // PDA bump (1 byte)
+}
+
+impl DailyMineState {
+    pub const LEN: usize = 8 + 8 + 8 + 1; // discriminator + fields
+}
+
+/// Global mining rate window (caps BOOTY minted per time window)
+#[account]
+pub struct MiningRateState {
+    pub window_start: i64,       // When the current window began (8 bytes)
+    pub minted_in_window: u64,   // BOOTY mined in the current window (8 bytes)
+    pub bump: u8,                // PDA bump (1 byte)
+}
+
+impl MiningRateState {
+    pub const LEN: usize = 8 + 8 + 8 + 1; // discriminator + fields
+}
+
+/// Linear BOOTY vesting schedule (one per beneficiary)
+#[account]
+pub struct VestingSchedule {
+    pub beneficiary: Pubkey, // Wallet receiving the vested BOOTY (32 bytes)
+    pub total: u64,          // Total BOOTY to vest (8 bytes)
+    pub claimed: u64,        // BOOTY already minted to the beneficiary (8 bytes)
+    pub start_ts: i64,       // When vesting starts (8 bytes)
+    pub cliff_ts: i64,       // No claims before this time (8 bytes)
+    pub duration: i64,       // Seconds from start until fully vested (8 bytes)
+    pub bump: u8,            // PDA bump (1 byte)
+}
+
+impl VestingSchedule {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1; // discriminator + fields
+}
+
+/// Open player-to-player trade offer (escrows the maker's asset)
+#[account]
+pub struct TradeOffer {
+    pub maker: Pubkey,         // Player who created the offer (32 bytes)
+    pub trade_id: u64,         // Maker-chosen ID, part of the PDA seeds (8 bytes)
+    pub offer_mint: Pubkey,    // Mint of the escrowed asset (32 bytes)
+    pub offer_amount: u64,     // Amount escrowed (8 bytes)
+    pub request_mint: Pubkey,  // Mint the maker wants in return (32 bytes)
+    pub request_amount: u64,   // Amount the maker wants in return (8 bytes)
+    pub bump: u8,              // PDA bump (1 byte)
+}
+
+impl TradeOffer {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 8 + 32 + 8 + 1; // discriminator + fields
+}
+
+/// Player stake record (one active stake per player)
+#[account]
+pub struct StakeRecord {
+    pub player: Pubkey,    // Player's wallet (32 bytes)
+    pub amount: u64,       // BOOTY staked (8 bytes)
+    pub start_ts: i64,     // When the stake started (8 bytes)
+    pub bump: u8,          // PDA bump (1 byte)
+}
+
+impl StakeRecord {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1; // discriminator + fields
+}
+
+// ====================================================================
+// ACCOUNT CONTEXTS (defines which accounts each instruction needs)
+// ====================================================================
+
+#[derive(Accounts)]
+pub struct MintNFT<'info> {
+    /// The player's wallet that will receive the NFT
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// The payer/authority (game backend wallet) that pays for and authorizes minting
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Registry of backend wallets allowed to mint
+    #[account(
+        seeds = [b"minters"],
+        bump = minter_registry.bump,
+        constraint = minter_registry.minters.contains(&payer.key()) @ ErrorCode::UnauthorizedMinter
+    )]
+    pub minter_registry: Account<'info, MintAuthorityRegistry>,
+
+    /// NFT symbol/name policy (enforced when it exists and is enabled)
+    /// CHECK: May not exist yet; deserialized by check_nft_policy when it does
+    #[account(seeds = [b"nft-policy"], bump)]
+    pub nft_policy: UncheckedAccount<'info>,
+
+    /// Royalty settings applied to every mint (used when it exists)
+    /// CHECK: May not exist yet; deserialized by royalty_terms when it does
+    #[account(seeds = [b"royalty-config"], bump)]
+    pub royalty_config: UncheckedAccount<'info>,
+
+    /// The mint account for this specific NFT
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = payer,
+        mint::freeze_authority = payer,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// The token account that will hold the NFT for the player
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = player,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Metaplex metadata account
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Metaplex master edition account (PDA of the mint)
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// Claimed treasure this NFT is the premium reward for (tier-derived mints only)
+    #[account(
+        mut,
+        constraint = treasure_record.player == player.key() @ ErrorCode::Unauthorized,
+        constraint = treasure_record.claimed @ ErrorCode::TreasureNotClaimed,
+        constraint = !treasure_record.nft_minted @ ErrorCode::NftAlreadyMinted
+    )]
+    pub treasure_record: Option<Account<'info, TreasureRecord>>,
+
+    /// Per-tier mint counts and caps, required for tiered mints (created on first use)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = TierSupply::LEN,
+        seeds = [b"tier-supply"],
+        bump
+    )]
+    pub tier_supply: Option<Account<'info, TierSupply>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metaplex>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> MintNFT<'info> {
+    /// Tier of the claimed treasure this NFT rewards, with the supply it counts
+    /// against; marks the treasure so it only ever backs one NFT
+    fn claimed_tier(&mut self) -> Result<Option<(u8, &mut TierSupply)>> {
+        let treasure_record = match self.treasure_record.as_mut() {
+            Some(treasure_record) => treasure_record,
+            None => return Ok(None),
+        };
+        treasure_record.nft_minted = true;
+
+        let tier_supply = self
+            .tier_supply
+            .as_mut()
+            .ok_or(ErrorCode::TierSupplyRequired)?;
+
+        Ok(Some((treasure_record.tier, tier_supply)))
+    }
+
+    /// Collect the accounts used by the shared NFT minting CPIs
+    fn nft_accounts(&self) -> NftMintAccounts<'info> {
+        NftMintAccounts {
+            player: self.player.key(),
+            payer: self.payer.to_account_info(),
+            mint: self.mint.to_account_info(),
+            token_account: self.token_account.to_account_info(),
+            metadata: self.metadata.to_account_info(),
+            master_edition: self.master_edition.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            token_metadata_program: self.token_metadata_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        }
+    }
+}
+
+/// Accounts needed to mint a single NFT, shared by mint_nft, collection
+/// minting and batch minting (where they come from remaining_accounts)
+struct NftMintAccounts<'info> {
+    player: Pubkey,
+    payer: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    metadata: AccountInfo<'info>,
+    master_edition: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    token_metadata_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: AccountInfo<'info>,
+}
+
+impl<'info> NftMintAccounts<'info> {
+    /// Mint the token, create its metadata and lock supply with a master edition
+    fn mint_with_metadata(
+        &self,
+        metadata_data: DataV2,
+        is_mutable: bool,
+        revoke_authorities: bool,
+        tier: Option<(u8, &mut TierSupply)>,
+    ) -> Result<()> {
+        validate_metadata(&metadata_data.name, &metadata_data.symbol, &metadata_data.uri)?;
+
+        // Tiered NFTs count against their tier's cap before anything is minted
+        let tier = match tier {
+            Some((tier, tier_supply)) => {
+                tier_supply.record_mint(tier)?;
+                Some(tier)
+            }
+            None => None,
+        };
+
+        // Mint 1 token to the player's token account
+        self.mint_token()?;
+
+        msg!("Token minted successfully");
+
+        let uri = metadata_data.uri.clone();
+        self.create_metadata(metadata_data, is_mutable)?;
+
+        msg!("Metadata created successfully");
+
+        emit!(CollectibleMinted {
+            player: self.player,
+            mint: self.mint.key(),
+            metadata: self.metadata.key(),
+            uri,
+            tier,
+        });
+
+        // Must happen before the master edition takes over the freeze authority
+        if revoke_authorities {
+            self.revoke_freeze_authority()?;
+            msg!("Freeze authority revoked");
+        }
+
+        // Lock supply at 1 so no further copies can ever be minted
+        self.create_master_edition()?;
+
+        msg!("Master edition created successfully");
+
+        Ok(())
+    }
+
+    /// Mint the single NFT token into the player's token account
+    fn mint_token(&self) -> Result<()> {
+        mint_to(
+            CpiContext::new(
+                self.token_program.clone(),
+                MintTo {
+                    authority: self.payer.clone(),
+                    to: self.token_account.clone(),
+                    mint: self.mint.clone(),
+                },
+            ),
+            1, // NFTs have a supply of 1
+        )
+    }
+
+    /// Create the Metaplex metadata account for the minted token
+    /// Mutable metadata can later be evolved via update_nft_metadata
+    fn create_metadata(&self, metadata_data: DataV2, is_mutable: bool) -> Result<()> {
+        create_metadata_accounts_v3(
+            CpiContext::new(
+                self.token_metadata_program.clone(),
+                CreateMetadataAccountsV3 {
+                    payer: self.payer.clone(),
+                    mint: self.mint.clone(),
+                    metadata: self.metadata.clone(),
+                    mint_authority: self.payer.clone(),
+                    update_authority: self.payer.clone(),
+                    system_program: self.system_program.clone(),
+                    rent: self.rent.clone(),
+                },
+            ),
+            metadata_data,
+            is_mutable,
+            true, // update_authority_is_signer
+            None, // collection_details
+        )
+    }
+
+    /// Remove the payer's freeze authority so nobody can freeze the holder's NFT
+    /// Mint authority needs no revoking: the master edition takes it over
+    fn revoke_freeze_authority(&self) -> Result<()> {
+        set_authority(
+            CpiContext::new(
+                self.token_program.clone(),
+                SetAuthority {
+                    current_authority: self.payer.clone(),
+                    account_or_mint: self.mint.clone(),
+                },
+            ),
+            AuthorityType::FreezeAccount,
+            None,
+        )
+    }
+
+    /// Create the master edition, which moves mint authority (and freeze
+    /// authority, if still set) to the edition PDA and caps supply so the
+    /// NFT stays a true 1-of-1 that the backend key can never inflate
+    fn create_master_edition(&self) -> Result<()> {
+        create_master_edition_v3(
+            CpiContext::new(
+                self.token_metadata_program.clone(),
+                CreateMasterEditionV3 {
+                    edition: self.master_edition.clone(),
+                    mint: self.mint.clone(),
+                    update_authority: self.payer.clone(),
+                    mint_authority: self.payer.clone(),
+                    payer: self.payer.clone(),
+                    metadata: self.metadata.clone(),
+                    token_program: self.token_program.clone(),
+                    system_program: self.system_program.clone(),
+                    rent: self.rent.clone(),
+                },
+            ),
+            Some(0), // max_supply (no prints allowed)
+        )
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct MintCollectibleInCollection<'info> {
+    /// Accounts for the collectible itself (same as mint_nft)
+    pub nft: MintNFT<'info>,
+
+    /// The collection NFT mint this collectible belongs to
+    pub collection_mint: Account<'info, Mint>,
+
+    /// Supply tracker of the collection
+    #[account(
+        mut,
+        seeds = [b"collection", collection_mint.key().as_ref()],
+        bump = collection_state.bump
+    )]
+    pub collection_state: Account<'info, CollectionState>,
+
+    /// Metaplex metadata account of the collection
+    /// CHECK: Validated by the Metaplex program during verification
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// Metaplex master edition account of the collection
+    /// CHECK: Validated by the Metaplex program during verification
+    pub collection_master_edition: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpgradeMonster<'info> {
+    /// Accounts for the upgraded monster (same as mint_nft)
+    pub nft: MintNFT<'info>,
+
+    /// Player burning the inputs (must be the recipient of the upgrade)
+    #[account(
+        constraint = owner.key() == nft.player.key() @ ErrorCode::Unauthorized
+    )]
+    pub owner: Signer<'info>,
+
+    /// First monster mint being burned
+    #[account(mut)]
+    pub input_mint_a: Account<'info, Mint>,
+
+    /// Player's token account holding the first monster
+    #[account(
+        mut,
+        constraint = input_token_account_a.owner == owner.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = input_token_account_a.mint == input_mint_a.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub input_token_account_a: Account<'info, TokenAccount>,
+
+    /// Metadata of the first monster (must come from an authorized minter)
+    #[account(
+        seeds = [
+            b"metadata",
+            nft.token_metadata_program.key().as_ref(),
+            input_mint_a.key().as_ref()
+        ],
+        seeds::program = nft.token_metadata_program.key(),
+        bump,
+        constraint = nft.minter_registry.minters.contains(&input_metadata_a.update_authority) @ ErrorCode::UnauthorizedMinter
+    )]
+    pub input_metadata_a: Account<'info, MetadataAccount>,
+
+    /// Second monster mint being burned
+    #[account(
+        mut,
+        constraint = input_mint_b.key() != input_mint_a.key() @ ErrorCode::MismatchedInputs
+    )]
+    pub input_mint_b: Account<'info, Mint>,
+
+    /// Player's token account holding the second monster
+    #[account(
+        mut,
+        constraint = input_token_account_b.owner == owner.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = input_token_account_b.mint == input_mint_b.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub input_token_account_b: Account<'info, TokenAccount>,
+
+    /// Metadata of the second monster (must come from an authorized minter)
+    #[account(
+        seeds = [
+            b"metadata",
+            nft.token_metadata_program.key().as_ref(),
+            input_mint_b.key().as_ref()
+        ],
+        seeds::program = nft.token_metadata_program.key(),
+        bump,
+        constraint = nft.minter_registry.minters.contains(&input_metadata_b.update_authority) @ ErrorCode::UnauthorizedMinter
+    )]
+    pub input_metadata_b: Account<'info, MetadataAccount>,
+}
+
+#[derive(Accounts)]
+pub struct MintNftBatch<'info> {
+    /// The player's wallet that will receive every NFT in the batch
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// The payer/authority (game backend wallet) that pays for and authorizes minting
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Registry of backend wallets allowed to mint
+    #[account(
+        seeds = [b"minters"],
+        bump = minter_registry.bump,
+        constraint = minter_registry.minters.contains(&payer.key()) @ ErrorCode::UnauthorizedMinter
+    )]
+    pub minter_registry: Account<'info, MintAuthorityRegistry>,
+
+    /// NFT symbol/name policy (enforced when it exists and is enabled)
+    /// CHECK: May not exist yet; deserialized by check_nft_policy when it does
+    #[account(seeds = [b"nft-policy"], bump)]
+    pub nft_policy: UncheckedAccount<'info>,
+
+    /// Royalty settings applied to every mint (used when it exists)
+    /// CHECK: May not exist yet; deserialized by royalty_terms when it does
+    #[account(seeds = [b"royalty-config"], bump)]
+    pub royalty_config: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metaplex>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> MintNftBatch<'info> {
+    /// Create a fresh NFT mint (payer as mint/freeze authority) and the
+    /// player's associated token account for it
+    fn init_mint_and_token_account(&self, nft: &NftMintAccounts<'info>) -> Result<()> {
+        // The system program rejects this unless the mint keypair signed
+        create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.payer.to_account_info(),
+                    to: nft.mint.clone(),
+                },
+            ),
+            Rent::get()?.minimum_balance(Mint::LEN),
+            Mint::LEN as u64,
+            &self.token_program.key(),
+        )?;
+
+        initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: nft.mint.clone(),
+                },
+            ),
+            0, // decimals
+            &self.payer.key(),
+            Some(&self.payer.key()),
+        )?;
+
+        // The associated token program rejects a token account that isn't the
+        // canonical ATA for (player, mint)
+        associated_token::create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: self.payer.to_account_info(),
+                associated_token: nft.token_account.clone(),
+                authority: self.player.to_account_info(),
+                mint: nft.mint.clone(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateCollection<'info> {
+    /// The payer/authority (game backend wallet) that owns the collection
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The mint account for the collection NFT
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = payer,
+        mint::freeze_authority = payer,
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    /// Supply tracker of the collection (created with it)
+    #[account(
+        init,
+        payer = payer,
+        space = CollectionState::LEN,
+        seeds = [b"collection", collection_mint.key().as_ref()],
+        bump
+    )]
+    pub collection_state: Account<'info, CollectionState>,
+
+    /// The token account that holds the collection NFT for the payer
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = collection_mint,
+        associated_token::authority = payer,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Metaplex metadata account for the collection
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Metaplex master edition account for the collection
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metaplex>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetNftPolicy<'info> {
+    /// NFT policy PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = NftPolicy::LEN,
+        seeds = [b"nft-policy"],
+        bump
+    )]
+    pub nft_policy: Account<'info, NftPolicy>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRoyaltyConfig<'info> {
+    /// Royalty config PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RoyaltyConfig::LEN,
+        seeds = [b"royalty-config"],
+        bump
+    )]
+    pub royalty_config: Account<'info, RoyaltyConfig>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTierCaps<'info> {
+    /// Tier supply PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TierSupply::LEN,
+        seeds = [b"tier-supply"],
+        bump
+    )]
+    pub tier_supply: Account<'info, TierSupply>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddMinter<'info> {
+    /// Minter registry PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MintAuthorityRegistry::LEN,
+        seeds = [b"minters"],
+        bump
+    )]
+    pub minter_registry: Account<'info, MintAuthorityRegistry>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveMinter<'info> {
+    /// Minter registry PDA
+    #[account(
+        mut,
+        seeds = [b"minters"],
+        bump = minter_registry.bump
+    )]
+    pub minter_registry: Account<'info, MintAuthorityRegistry>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAllowlistRoot<'info> {
+    /// Allowlist PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MerkleAllowlist::LEN,
+        seeds = [b"allowlist"],
+        bump
+    )]
+    pub allowlist: Account<'info, MerkleAllowlist>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proof: Vec<[u8; 32]>, leaf: [u8; 32], leaf_index: u32)]
+pub struct MintCollectibleGated<'info> {
+    /// Allowlisted player minting (and paying for) the NFT
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Allowlist PDA holding the Merkle root
+    #[account(
+        seeds = [b"allowlist"],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Account<'info, MerkleAllowlist>,
+
+    /// Claim bitmap covering leaf_index (per root, created on first use)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = AllowlistClaims::LEN,
+        seeds = [
+            b"allowlist-claims",
+            allowlist.root.as_ref(),
+            &(leaf_index / ALLOWLIST_LEAVES_PER_BITMAP).to_le_bytes()
+        ],
+        bump
+    )]
+    pub allowlist_claims: Account<'info, AllowlistClaims>,
+
+    /// NFT symbol/name policy (enforced when it exists and is enabled)
+    /// CHECK: May not exist yet; deserialized by check_nft_policy when it does
+    #[account(seeds = [b"nft-policy"], bump)]
+    pub nft_policy: UncheckedAccount<'info>,
+
+    /// Royalty settings applied to every mint (used when it exists)
+    /// CHECK: May not exist yet; deserialized by royalty_terms when it does
+    #[account(seeds = [b"royalty-config"], bump)]
+    pub royalty_config: UncheckedAccount<'info>,
+
+    /// The mint account for this specific NFT
+    #[account(
+        init,
+        payer = player,
+        mint::decimals = 0,
+        mint::authority = player,
+        mint::freeze_authority = player,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// The token account that will hold the NFT for the player
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = mint,
+        associated_token::authority = player,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Metaplex metadata account
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Metaplex master edition account (PDA of the mint)
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metaplex>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> MintCollectibleGated<'info> {
+    /// Collect the accounts used by the shared NFT minting CPIs
+    fn nft_accounts(&self) -> NftMintAccounts<'info> {
+        NftMintAccounts {
+            player: self.player.key(),
+            payer: self.player.to_account_info(),
+            mint: self.mint.to_account_info(),
+            token_account: self.token_account.to_account_info(),
+            metadata: self.metadata.to_account_info(),
+            master_edition: self.master_edition.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            token_metadata_program: self.token_metadata_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateNftMetadata<'info> {
+    /// The update authority recorded on the NFT's metadata
+    pub update_authority: Signer<'info>,
+
+    /// The NFT mint whose metadata is being updated
+    pub mint: Account<'info, Mint>,
+
+    /// Metaplex metadata account (must be mutable and owned by update_authority)
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+        constraint = metadata.update_authority == update_authority.key() @ ErrorCode::Unauthorized,
+        constraint = metadata.is_mutable @ ErrorCode::ImmutableMetadata
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+
+    pub token_metadata_program: Program<'info, Metaplex>,
+}
+
+#[derive(Accounts)]
+#[instruction(vault_id: u64)]
+pub struct InitializeVault<'info> {
+    /// Vault PDA that stores program config
+    #[account(
+        init,
+        payer = authority,
+        space = TreasureVault::LEN,
+        seeds = [b"vault".as_ref(), vault_id_seed(vault_id).as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority (pays for the vault)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(booty_decimals: u8)]
+pub struct InitializeGame<'info> {
+    /// Program config PDA holding the admin settings
+    #[account(
+        init,
+        payer = authority,
+        space = ProgramConfig::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Default vault PDA (vault_id 0)
+    #[account(
+        init,
+        payer = authority,
+        space = TreasureVault::LEN,
+        seeds = [b"vault".as_ref(), vault_id_seed(0).as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// The BOOTY token mint (legacy SPL Token or Token-2022)
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = booty_decimals,
+        mint::authority = booty_state,
+        mint::freeze_authority = booty_state,
+        mint::token_program = token_program,
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// BOOTY state PDA that tracks supply
+    #[account(
+        init,
+        payer = authority,
+        space = BootyState::LEN,
+        seeds = [b"booty-state"],
+        bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    /// Admin who deploys the game
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// Original vault (vault_id 0), possibly still in its pre-versioning layout
+    /// CHECK: Discriminator and stored authority are verified in the instruction
+    #[account(
+        seeds = [b"vault"],
+        bump,
+        owner = crate::ID
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Program config PDA being created
+    #[account(
+        init,
+        payer = authority,
+        space = ProgramConfig::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Vault authority (pays for the config PDA)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, treasure_id: i64)]
+pub struct HideTreasure<'info> {
+    /// Player hiding the treasure
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Player's token account (source of tokens)
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// Vault's token account (destination)
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = vault_token_account.mint == player_token_account.mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Mint of the token being hidden
+    #[account(
+        constraint = token_mint.key() == player_token_account.mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Protocol fee token account for this mint (vault PDA is the owner)
+    #[account(
+        init_if_needed,
+        payer = player,
+        seeds = [b"fees".as_ref(), vault.id_seed().as_ref(), token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = vault,
+    )]
+    pub fee_token_account: Account<'info, TokenAccount>,
+
+    /// Whitelist entry for the token being hidden (provides its decimals)
+    #[account(
+        seeds = [b"whitelist", player_token_account.mint.as_ref()],
+        bump = whitelist.bump,
+        constraint = whitelist.enabled @ ErrorCode::TokenNotWhitelisted
+    )]
+    pub whitelist: Account<'info, TokenWhitelist>,
+
+    /// Pyth price feed for the token (required when the whitelist has one set)
+    /// CHECK: Key is validated against whitelist.price_feed and data is parsed manually
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Vault PDA
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (pause flag, fees and deposit limits)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Treasure record PDA (unique per season, per player, per treasure)
+    /// Using treasure_id as seed to allow multiple treasures per player
+    #[account(
+        init,
+        payer = player,
+        space = TreasureRecord::LEN,
+        seeds = [
+            b"treasure".as_ref(),
+            vault.id_seed().as_ref(),
+            &vault.season.to_le_bytes(),
+            player.key().as_ref(),
+            &treasure_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub treasure_record: Account<'info, TreasureRecord>,
+
+    /// Player's used treasure_id bitmap shard covering this treasure_id
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = ClaimedNonces::LEN,
+        seeds = [
+            b"nonces".as_ref(),
+            player.key().as_ref(),
+            &ClaimedNonces::shard_of(treasure_id).to_le_bytes()
+        ],
+        bump
+    )]
+    pub claimed_nonces: Account<'info, ClaimedNonces>,
+
+    /// Player's lifetime stats PDA (created on first activity)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [b"profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// Global leaderboard PDA (created on first hide)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = Leaderboard::LEN,
+        seeds = [b"leaderboard"],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintDepositReceipt<'info> {
+    /// Player who hid the treasure (pays for and receives the receipt)
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Treasure record the receipt proves (still held in this vault)
+    #[account(
+        constraint = treasure_record.player == player.key() @ ErrorCode::Unauthorized,
+        constraint = !treasure_record.claimed @ ErrorCode::AlreadyClaimed,
+        constraint = !treasure_record.reclaimed @ ErrorCode::AlreadyReclaimed,
+        constraint = treasure_record.vault_id == vault.vault_id @ ErrorCode::VaultMismatch
+    )]
+    pub treasure_record: Account<'info, TreasureRecord>,
+
+    /// Vault PDA (mint, freeze and update authority of every receipt)
+    #[account(
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Receipt mint (one per treasure record, so a second receipt fails)
+    #[account(
+        init,
+        payer = player,
+        seeds = [b"receipt", treasure_record.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = vault,
+        mint::freeze_authority = vault,
+    )]
+    pub receipt_mint: Account<'info, Mint>,
+
+    /// Player's token account for the receipt (frozen after minting)
+    #[account(
+        init,
+        payer = player,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = player,
+    )]
+    pub receipt_token_account: Account<'info, TokenAccount>,
+
+    /// Metaplex metadata account of the receipt
+    /// CHECK: Address is seeds-checked; created by the Metaplex program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            receipt_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Metaplex master edition account of the receipt
+    /// CHECK: Address is seeds-checked; created by the Metaplex program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            receipt_mint.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metaplex>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTreasure<'info> {
+    /// Player claiming the treasure
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Treasure record being claimed
+    #[account(
+        mut,
+        constraint = treasure_record.player == player.key() @ ErrorCode::Unauthorized,
+        constraint = !treasure_record.claimed @ ErrorCode::AlreadyClaimed,
+        constraint = !treasure_record.reclaimed @ ErrorCode::AlreadyReclaimed,
+        constraint = treasure_record.vault_id == vault.vault_id @ ErrorCode::VaultMismatch
+    )]
+    pub treasure_record: Account<'info, TreasureRecord>,
+
+    /// Vault PDA
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (pause flag and claim delay)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Tier reward curve (a reward is owed before claiming when it exists)
+    /// CHECK: May not exist; only its owner and discriminator are inspected
+    #[account(seeds = [b"reward-curve"], bump)]
+    pub reward_curve: UncheckedAccount<'info>,
+
+    /// Player's lifetime stats PDA (created on first activity)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [b"profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTreasureBatch<'info> {
+    /// Player claiming the treasures
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Vault PDA
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (pause flag and claim delay)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Tier reward curve (a reward is owed before claiming when it exists)
+    /// CHECK: May not exist; only its owner and discriminator are inspected
+    #[account(seeds = [b"reward-curve"], bump)]
+    pub reward_curve: UncheckedAccount<'info>,
+
+    /// Player's lifetime stats PDA (created on first activity)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [b"profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdminForceClaim<'info> {
+    /// Treasure record being claimed on the player's behalf
+    #[account(
+        mut,
+        constraint = !treasure_record.claimed @ ErrorCode::AlreadyClaimed,
+        constraint = !treasure_record.reclaimed @ ErrorCode::AlreadyReclaimed,
+        constraint = treasure_record.vault_id == vault.vault_id @ ErrorCode::VaultMismatch
+    )]
+    pub treasure_record: Account<'info, TreasureRecord>,
+
+    /// Vault PDA
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WaiveTreasureReward<'info> {
+    /// Player who hid the treasure
+    pub player: Signer<'info>,
+
+    /// Treasure record whose reward is given up
+    #[account(
+        mut,
+        constraint = treasure_record.player == player.key() @ ErrorCode::Unauthorized,
+        constraint = !treasure_record.rewarded @ ErrorCode::AlreadyRewarded
+    )]
+    pub treasure_record: Account<'info, TreasureRecord>,
+}
+
+#[derive(Accounts)]
+pub struct CheckClaimable<'info> {
+    /// Treasure record being checked
+    pub treasure_record: Account<'info, TreasureRecord>,
+
+    /// Program config PDA (pause flag and claim delay)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Tier reward curve (a reward is owed before claiming when it exists)
+    /// CHECK: May not exist; only its owner and discriminator are inspected
+    #[account(seeds = [b"reward-curve"], bump)]
+    pub reward_curve: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(note: String)]
+pub struct AnnotateTreasure<'info> {
+    /// Player who hid the treasure (pays for any extra space)
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Treasure record being annotated (resized to fit the note)
+    #[account(
+        mut,
+        realloc = TreasureRecord::space_with_note(note.len()),
+        realloc::payer = player,
+        realloc::zero = false,
+        constraint = treasure_record.player == player.key() @ ErrorCode::Unauthorized
+    )]
+    pub treasure_record: Account<'info, TreasureRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimTreasure<'info> {
+    /// Player reclaiming their treasure
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Treasure record being reclaimed (closed once fully reclaimed, rent returned to the player)
+    #[account(
+        mut,
+        constraint = treasure_record.player == player.key() @ ErrorCode::Unauthorized,
+        constraint = !treasure_record.claimed @ ErrorCode::AlreadyClaimed,
+        constraint = !treasure_record.rewarded @ ErrorCode::AlreadyRewarded,
+        constraint = treasure_record.vault_id == vault.vault_id @ ErrorCode::VaultMismatch
+    )]
+    pub treasure_record: Account<'info, TreasureRecord>,
+
+    /// Player's token account (destination of the refund, must be the one the deposit came from)
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = player_token_account.mint == treasure_record.token_mint @ ErrorCode::InvalidTokenAccount,
+        constraint = player_token_account.key() == treasure_record.source_token_account @ ErrorCode::SourceAccountMismatch
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// Vault's token account (source of the refund)
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = vault_token_account.mint == treasure_record.token_mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Vault PDA (signs the transfer out of its token account)
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (checked for the emergency pause)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(x: i32, y: i32, search_id: i64)]
+pub struct SearchTreasure<'info> {
+    /// Player searching for treasure
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// BOOTY token mint
+    #[account(
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// Player's BOOTY token account (source of search fee payment)
+    #[account(
+        mut,
+        constraint = player_booty_account.owner == player.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = player_booty_account.mint == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub player_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// Vault's BOOTY token account (destination for search fees)
+    #[account(mut)]
+    pub vault_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// BOOTY state PDA (provides the mint and its decimals)
+    #[account(
+        seeds = [b"booty-state"],
+        bump = booty_state.bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    /// Vault PDA (provides the current season)
+    #[account(
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (id ordering flag)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Search record PDA (unique per season, per player, per search)
+    #[account(
+        init,
+        payer = player,
+        space = SearchRecord::LEN,
+        seeds = [
+            b"search".as_ref(),
+            vault.id_seed().as_ref(),
+            &vault.season.to_le_bytes(),
+            player.key().as_ref(),
+            &search_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub search_record: Account<'info, SearchRecord>,
+
+    /// Player's lifetime stats PDA (created on first activity)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [b"profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SearchTreasureBatch<'info> {
+    /// Player searching for treasure
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// BOOTY token mint
+    #[account(
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// Player's BOOTY token account (source of search fee payment)
+    #[account(
+        mut,
+        constraint = player_booty_account.owner == player.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = player_booty_account.mint == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub player_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// Vault's BOOTY token account (destination for search fees)
+    #[account(mut)]
+    pub vault_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// BOOTY state PDA (provides the mint and its decimals)
+    #[account(
+        seeds = [b"booty-state"],
+        bump = booty_state.bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    /// Vault PDA (provides the current season)
+    #[account(
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (id ordering flag)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Player's lifetime stats PDA (created on first activity)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [b"profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SearchTreasureBatch<'info> {
+    /// Create and fill one search record PDA, using the same seeds as search_treasure
+    fn init_search_record(
+        &self,
+        search_record: &AccountInfo<'info>,
+        coord: &SearchCoord,
+        search_id: i64,
+    ) -> Result<()> {
+        let id_seed = self.vault.id_seed();
+        let season = self.vault.season.to_le_bytes();
+        let player = self.player.key();
+        let search_id_bytes = search_id.to_le_bytes();
+        let (expected, bump) = Pubkey::find_program_address(
+            &[b"search".as_ref(), &id_seed, &season, player.as_ref(), &search_id_bytes],
+            &crate::ID,
+        );
+        require_keys_eq!(search_record.key(), expected, ErrorCode::InvalidSearchRecord);
+
+        let seeds = &[
+            b"search".as_ref(),
+            &id_seed,
+            &season,
+            player.as_ref(),
+            &search_id_bytes,
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        create_account(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.player.to_account_info(),
+                    to: search_record.clone(),
+                },
+                signer_seeds,
+            ),
+            Rent::get()?.minimum_balance(SearchRecord::LEN),
+            SearchRecord::LEN as u64,
+            &crate::ID,
+        )?;
+
+        let record = SearchRecord {
+            player,
+            x: coord.x,
+            y: coord.y,
+            timestamp: search_id,
+            found: false,
+            bump,
+            created_ts: Clock::get()?.unix_timestamp,
+        };
+        let mut data = search_record.try_borrow_mut_data()?;
+        record.try_serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct VerifyCollectibleOwnership<'info> {
+    /// Player proving ownership
+    pub player: Signer<'info>,
+
+    /// Player's token account holding the collectible
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Metadata of the collectible
+    #[account(
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            token_account.mint.as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+
+    /// Registry of backend wallets allowed to mint
+    #[account(
+        seeds = [b"minters"],
+        bump = minter_registry.bump
+    )]
+    pub minter_registry: Account<'info, MintAuthorityRegistry>,
+
+    pub token_metadata_program: Program<'info, Metaplex>,
+}
+
+#[derive(Accounts)]
+#[instruction(x: i32, y: i32, search_id: i64)]
+pub struct UseCollectible<'info> {
+    /// Player spending the collectible
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Mint of the consumable collectible (burned on its last use)
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    /// Player's token account holding the collectible
+    #[account(
+        mut,
+        constraint = token_account.owner == player.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = token_account.mint == mint.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Metadata of the collectible (must come from an authorized minter)
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+        constraint = minter_registry.minters.contains(&metadata.update_authority) @ ErrorCode::UnauthorizedMinter
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+
+    /// Registry of backend wallets allowed to mint
+    #[account(
+        seeds = [b"minters"],
+        bump = minter_registry.bump
+    )]
+    pub minter_registry: Account<'info, MintAuthorityRegistry>,
+
+    /// Vault PDA (provides the current season)
+    #[account(
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Search record PDA (same seeds as a paid search)
+    #[account(
+        init,
+        payer = player,
+        space = SearchRecord::LEN,
+        seeds = [
+            b"search".as_ref(),
+            vault.id_seed().as_ref(),
+            &vault.season.to_le_bytes(),
+            player.key().as_ref(),
+            &search_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub search_record: Account<'info, SearchRecord>,
+
+    /// Player's lifetime stats PDA (created on first activity)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [b"profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metaplex>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(search_id: i64)]
+pub struct VerifyFind<'info> {
+    /// Player who made the search
+    pub player: SystemAccount<'info>,
+
+    /// Search record PDA being verified
+    #[account(
+        mut,
+        seeds = [
+            b"search".as_ref(),
+            vault.id_seed().as_ref(),
+            &vault.season.to_le_bytes(),
+            player.key().as_ref(),
+            &search_id.to_le_bytes()
+        ],
+        bump = search_record.bump
+    )]
+    pub search_record: Account<'info, SearchRecord>,
+
+    /// Treasure being found (records its first finder)
+    #[account(
+        mut,
+        constraint = treasure_record.vault_id == vault.vault_id @ ErrorCode::VaultMismatch
+    )]
+    pub treasure_record: Account<'info, TreasureRecord>,
+
+    /// Vault PDA (provides the current season)
+    #[account(
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// BOOTY token mint
+    #[account(
+        mut,
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// Player's BOOTY token account (auto-created if needed)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = booty_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program,
+    )]
+    pub player_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// BOOTY state PDA (mint authority)
+    #[account(
+        mut,
+        seeds = [b"booty-state"],
+        bump = booty_state.bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    /// Admin authority (pays for the player's token account if needed)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WhitelistToken<'info> {
+    /// Whitelist PDA for this token
+    #[account(
+        init,
+        payer = authority,
+        space = TokenWhitelist::LEN,
+        seeds = [b"whitelist", token_mint.key().as_ref()],
+        bump
+    )]
+    pub whitelist: Account<'info, TokenWhitelist>,
+
+    /// Index of enabled whitelisted mints (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = WhitelistIndex::LEN,
+        seeds = [b"whitelist-index"],
+        bump
+    )]
+    pub whitelist_index: Account<'info, WhitelistIndex>,
+
+    /// Token mint being whitelisted (read for its decimals)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WhitelistTokensBatch<'info> {
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Index of enabled whitelisted mints (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = WhitelistIndex::LEN,
+        seeds = [b"whitelist-index"],
+        bump
+    )]
+    pub whitelist_index: Account<'info, WhitelistIndex>,
+
+    /// Admin authority (pays for every whitelist PDA)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WhitelistTokensBatch<'info> {
+    /// Create one whitelist PDA for `mint`, returning the mint's decimals
+    fn init_whitelist_entry(
+        &self,
+        whitelist: &AccountInfo<'info>,
+        mint: &AccountInfo<'info>,
+    ) -> Result<u8> {
+        require_keys_eq!(*mint.owner, token::ID, ErrorCode::InvalidTokenAccount);
+        let decimals = Mint::try_deserialize(&mut &mint.try_borrow_data()?[..])?.decimals;
+
+        let mint_key = mint.key();
+        let (expected, bump) =
+            Pubkey::find_program_address(&[b"whitelist", mint_key.as_ref()], &crate::ID);
+        require_keys_eq!(whitelist.key(), expected, ErrorCode::InvalidWhitelistEntry);
+
+        let seeds = &[b"whitelist".as_ref(), mint_key.as_ref(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        create_account(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.authority.to_account_info(),
+                    to: whitelist.clone(),
+                },
+                signer_seeds,
+            ),
+            Rent::get()?.minimum_balance(TokenWhitelist::LEN),
+            TokenWhitelist::LEN as u64,
+            &crate::ID,
+        )?;
+
+        let entry = TokenWhitelist::new(mint_key, decimals, bump);
+        let mut data = whitelist.try_borrow_mut_data()?;
+        entry.try_serialize(&mut &mut data[..])?;
+
+        Ok(decimals)
+    }
+}
+
+#[derive(Accounts)]
+pub struct RefreshWhitelistDecimals<'info> {
+    /// Whitelist PDA being refreshed (grown if it still has the legacy layout)
+    /// CHECK: Discriminator, PDA seeds and mint are verified in the instruction
+    #[account(mut, owner = crate::ID)]
+    pub whitelist: UncheckedAccount<'info>,
+
+    /// Mint of the whitelisted token (source of the decimals)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority (pays the rent of a grown entry)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshBootyDecimals<'info> {
+    /// BOOTY state PDA being refreshed
+    #[account(
+        mut,
+        seeds = [b"booty-state"],
+        bump = booty_state.bump,
+        constraint = booty_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    /// BOOTY token mint (source of the decimals)
+    #[account(
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// BOOTY admin authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateWhitelist<'info> {
+    /// Whitelist PDA being updated
+    #[account(
+        mut,
+        seeds = [b"whitelist", whitelist.token_mint.as_ref()],
+        bump = whitelist.bump
+    )]
+    pub whitelist: Account<'info, TokenWhitelist>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetWhitelistEnabled<'info> {
+    /// Whitelist PDA being enabled or disabled
+    #[account(
+        mut,
+        seeds = [b"whitelist", whitelist.token_mint.as_ref()],
+        bump = whitelist.bump
+    )]
+    pub whitelist: Account<'info, TokenWhitelist>,
+
+    /// Index of enabled whitelisted mints (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = WhitelistIndex::LEN,
+        seeds = [b"whitelist-index"],
+        bump
+    )]
+    pub whitelist_index: Account<'info, WhitelistIndex>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority (pays for the index if it doesn't exist yet)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseWhitelistEntry<'info> {
+    /// Whitelist PDA being closed (rent refunded to authority)
+    #[account(
+        mut,
+        seeds = [b"whitelist", whitelist.token_mint.as_ref()],
+        bump = whitelist.bump,
+        constraint = !whitelist.enabled @ ErrorCode::MustDisableFirst,
+        close = authority
+    )]
+    pub whitelist: Account<'info, TokenWhitelist>,
+
+    /// Index of enabled whitelisted mints (the closed mint is dropped from it)
+    #[account(
+        mut,
+        seeds = [b"whitelist-index"],
+        bump = whitelist_index.bump
+    )]
+    pub whitelist_index: Account<'info, WhitelistIndex>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    /// Vault PDA being closed (rent refunded to authority)
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        close = authority
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Vault's token account (must be empty)
+    #[account(
+        constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// Program account being upgraded to the current layout
+    /// CHECK: Discriminator and layout length are validated in the instruction
+    #[account(mut, owner = crate::ID)]
+    pub target: UncheckedAccount<'info>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority (pays any extra rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTreasureRecord<'info> {
+    /// Treasure record still in the original layout
+    /// CHECK: Discriminator, size and PDA seeds are verified in the instruction
+    #[account(mut, owner = crate::ID)]
+    pub treasure_record: UncheckedAccount<'info>,
+
+    /// Mint of the tokens the record's player hid
+    pub token_mint: Account<'info, Mint>,
+
+    /// Player's token account for that mint (becomes the reclaim destination)
+    #[account(
+        constraint = source_token_account.mint == token_mint.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub source_token_account: Account<'info, TokenAccount>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority (pays the rent of the grown record)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateVault<'info> {
+    /// Vault PDA
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Current admin authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// Program config PDA
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Current admin authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVaultTokens<'info> {
+    /// Vault PDA (signs the transfer out of its token account)
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Vault's token account (source)
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Token account receiving the withdrawn tokens
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == vault_token_account.mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    /// Admin authority
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeDrain<'info> {
+    /// Vault PDA (owner of the drained token account)
+    #[account(
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Vault's token account to be drained
+    #[account(
+        constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Token account that will receive the balance
+    #[account(
+        constraint = destination_token_account.mint == vault_token_account.mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    /// Pending drain PDA (one per vault token account)
+    #[account(
+        init,
+        payer = authority,
+        space = DrainProposal::LEN,
+        seeds = [b"drain", vault_token_account.key().as_ref()],
+        bump
+    )]
+    pub drain_proposal: Account<'info, DrainProposal>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDrain<'info> {
+    /// Vault PDA (signs the transfer out of its token account)
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Vault's token account being drained
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Destination recorded by propose_drain
+    #[account(
+        mut,
+        constraint = destination_token_account.key() == drain_proposal.destination @ ErrorCode::InvalidTokenAccount
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    /// Pending drain PDA (closed once executed, rent returned to the authority)
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"drain", vault_token_account.key().as_ref()],
+        bump = drain_proposal.bump
+    )]
+    pub drain_proposal: Account<'info, DrainProposal>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDrain<'info> {
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Pending drain PDA being cancelled (rent returned to the authority)
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"drain", drain_proposal.vault_token_account.as_ref()],
+        bump = drain_proposal.bump
+    )]
+    pub drain_proposal: Account<'info, DrainProposal>,
+
+    /// Admin authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReassignVaultTokenAuthority<'info> {
+    /// Vault PDA (signs as current owner of the token account)
+    #[account(
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority and pause flag)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Vault's token account being handed over
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Admin authority
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct BurnVaultTokens<'info> {
+    /// Vault PDA (signs the burn as owner of its token account)
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Vault's token account (tokens are burned from here)
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = vault_token_account.mint == token_mint.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Mint of the burned tokens (its supply shrinks)
+    #[account(mut)]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Admin authority
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SweepExpired<'info> {
+    /// Player who hid the treasure (receives the record's rent)
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// Expired treasure record being forfeited
+    #[account(
+        mut,
+        close = player,
+        constraint = treasure_record.player == player.key() @ ErrorCode::Unauthorized,
+        constraint = !treasure_record.claimed @ ErrorCode::AlreadyClaimed,
+        constraint = treasure_record.vault_id == vault.vault_id @ ErrorCode::VaultMismatch
+    )]
+    pub treasure_record: Account<'info, TreasureRecord>,
+
+    /// Vault's token account (source of the forfeited tokens)
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = vault_token_account.mint == treasure_record.token_mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Protocol fee token account for the mint (treasury destination)
+    #[account(
+        mut,
+        seeds = [b"fees".as_ref(), vault.id_seed().as_ref(), treasure_record.token_mint.as_ref()],
+        bump
+    )]
+    pub fee_token_account: Account<'info, TokenAccount>,
+
+    /// Vault PDA (signs the transfer out of its token account)
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Admin authority
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    /// Vault PDA (signs the transfer out of its token account)
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Vault's token account holding the dust (not the fee account itself)
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = vault_token_account.key() != fee_token_account.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Protocol fee token account for the mint (treasury destination)
+    #[account(
+        mut,
+        seeds = [b"fees".as_ref(), vault.id_seed().as_ref(), vault_token_account.mint.as_ref()],
+        bump
+    )]
+    pub fee_token_account: Account<'info, TokenAccount>,
+
+    /// Index of enabled whitelisted mints (dust is only measurable with one)
+    #[account(
+        seeds = [b"whitelist-index"],
+        bump = whitelist_index.bump
+    )]
+    pub whitelist_index: Account<'info, WhitelistIndex>,
+
+    /// Admin authority
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    /// Vault PDA (owns the fee accounts and signs the transfer)
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TreasureVault>,
+
+    /// Program config PDA (holds the admin authority)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Protocol fee token account for the mint (source)
+    #[account(
+        mut,
+        seeds = [b"fees".as_ref(), vault.id_seed().as_ref(), fee_token_account.mint.as_ref()],
+        bump
+    )]
+    pub fee_token_account: Account<'info, TokenAccount>,
+
+    /// Token account receiving the fees
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == fee_token_account.mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    /// Admin authority
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    /// Program config PDA
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.pending_authority == Some(new_authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Proposed admin accepting the transfer
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct InitializeBootyMint<'info> {
+    /// The BOOTY token mint (legacy SPL Token or Token-2022)
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = decimals,
+        mint::authority = booty_state,
+        mint::freeze_authority = booty_state,
+        mint::token_program = token_program,
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// BOOTY state PDA that tracks supply
+    #[account(
+        init,
+        payer = authority,
+        space = BootyState::LEN,
+        seeds = [b"booty-state"],
+        bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    /// Admin who initializes the BOOTY token
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MineBooty<'info> {
+    /// Player receiving BOOTY tokens
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// BOOTY token mint
+    #[account(
+        mut,
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// Player's BOOTY token account (auto-created if needed)
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = booty_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program,
+    )]
+    pub player_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// BOOTY state PDA
+    #[account(
+        mut,
+        seeds = [b"booty-state"],
+        bump = booty_state.bump,
+        constraint = booty_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    /// BOOTY admin authority (co-signs every mine)
+    pub authority: Signer<'info>,
+
+    /// Program config PDA (checked for the emergency pause)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// BOOTY multisig PDA (when it exists, its threshold of signers must approve)
+    /// CHECK: May not exist yet; deserialized by check_booty_multisig when it does
+    #[account(seeds = [b"booty-multisig"], bump)]
+    pub booty_multisig: UncheckedAccount<'info>,
+
+    /// Player's lifetime stats PDA (created on first activity)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [b"profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// Global mining rate window PDA (created on first mine)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = MiningRateState::LEN,
+        seeds = [b"mining-rate"],
+        bump
+    )]
+    pub mining_rate_state: Account<'info, MiningRateState>,
+
+    /// Player's daily mining tally PDA (created on first mine)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = DailyMineState::LEN,
+        seeds = [b"daily-mine", player.key().as_ref()],
+        bump
+    )]
+    pub daily_mine_state: Account<'info, DailyMineState>,
+
+    /// Player's last-mine timestamp PDA (created on first mine)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = MintTimestamp::LEN,
+        seeds = [b"mint-ts", player.key().as_ref()],
+        bump
+    )]
+    pub mint_timestamp: Account<'info, MintTimestamp>,
+
+    /// Referral bonus configuration (bonus is skipped when absent)
+    #[account(
+        seeds = [b"referral-config"],
+        bump = referral_config.bump
+    )]
+    pub referral_config: Option<Account<'info, ReferralConfig>>,
+
+    /// Referrer's BOOTY token account (required when a bonus is due)
+    #[account(
+        mut,
+        constraint = Some(referrer_booty_account.owner) == player_profile.referrer @ ErrorCode::InvalidTokenAccount,
+        constraint = referrer_booty_account.mint == booty_state.mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub referrer_booty_account: Option<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BurnBootyForTravel<'info> {
+    /// Player burning BOOTY tokens
+    pub player: Signer<'info>,
+
+    /// BOOTY token mint
+    #[account(
+        mut,
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// Player's BOOTY token account
+    #[account(
+        mut,
+        constraint = player_booty_account.owner == player.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = player_booty_account.mint == booty_state.mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub player_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// BOOTY state PDA
+    #[account(
+        mut,
+        seeds = [b"booty-state"],
+        bump = booty_state.bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    /// Program config PDA (checked for the emergency pause)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Player's last-mine timestamp PDA (hold time is enforced when it exists)
+    /// CHECK: May not exist yet; deserialized by check_hold_time when it does
+    #[account(seeds = [b"mint-ts", player.key().as_ref()], bump)]
+    pub mint_timestamp: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct IsInitialized<'info> {
+    /// Vault PDA (may not exist yet)
+    /// CHECK: Only its owner and discriminator are inspected
+    #[account(seeds = [b"vault"], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// BOOTY state PDA (may not exist yet)
+    /// CHECK: Only its owner and discriminator are inspected
+    #[account(seeds = [b"booty-state"], bump)]
+    pub booty_state: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(token_mint: Pubkey)]
+pub struct CheckWhitelist<'info> {
+    /// Whitelist PDA for the mint
+    /// CHECK: May not exist; deserialized by check_whitelist when it does
+    #[account(seeds = [b"whitelist", token_mint.as_ref()], bump)]
+    pub whitelist: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewRewards<'info> {
+    /// Tier reward curve
+    #[account(
+        seeds = [b"reward-curve"],
+        bump = reward_curve.bump
+    )]
+    pub reward_curve: Account<'info, RewardCurve>,
+
+    /// BOOTY state PDA (provides the mining bonus window)
+    #[account(
+        seeds = [b"booty-state"],
+        bump = booty_state.bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+}
+
+#[derive(Accounts)]
+pub struct ListWhitelistedTokens<'info> {
+    /// Index of enabled whitelisted mints
+    #[account(
+        seeds = [b"whitelist-index"],
+        bump = whitelist_index.bump
+    )]
+    pub whitelist_index: Account<'info, WhitelistIndex>,
+}
+
+#[derive(Accounts)]
+pub struct ReadSupply<'info> {
+    /// BOOTY state PDA
+    #[account(
+        seeds = [b"booty-state"],
+        bump = booty_state.bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+}
+
+#[derive(Accounts)]
+pub struct MineBootyForTreasure<'info> {
+    /// Player who hid the treasure (receives the reward)
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Treasure record the reward is computed from
+    #[account(
+        mut,
+        constraint = treasure_record.player == player.key() @ ErrorCode::Unauthorized,
+        constraint = !treasure_record.rewarded @ ErrorCode::AlreadyRewarded,
+        constraint = !treasure_record.reward_waived @ ErrorCode::RewardWaived,
+        constraint = !treasure_record.reclaimed @ ErrorCode::AlreadyReclaimed
+    )]
+    pub treasure_record: Account<'info, TreasureRecord>,
+
+    /// Tier reward curve
+    #[account(
+        seeds = [b"reward-curve"],
+        bump = reward_curve.bump
+    )]
+    pub reward_curve: Account<'info, RewardCurve>,
+
+    /// BOOTY token mint
+    #[account(
+        mut,
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// Player's BOOTY token account (auto-created if needed)
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = booty_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program,
+    )]
+    pub player_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// BOOTY state PDA
+    #[account(
+        mut,
+        seeds = [b"booty-state"],
+        bump = booty_state.bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    /// Program config PDA (checked for the emergency pause)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Player's lifetime stats PDA (created on first activity)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [b"profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// Global mining rate window PDA (created on first mine)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = MiningRateState::LEN,
+        seeds = [b"mining-rate"],
+        bump
+    )]
+    pub mining_rate_state: Account<'info, MiningRateState>,
+
+    /// Player's daily mining tally PDA (created on first mine)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = DailyMineState::LEN,
+        seeds = [b"daily-mine", player.key().as_ref()],
+        bump
+    )]
+    pub daily_mine_state: Account<'info, DailyMineState>,
+
+    /// Player's last-mine timestamp PDA (created on first mine)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = MintTimestamp::LEN,
+        seeds = [b"mint-ts", player.key().as_ref()],
+        bump
+    )]
+    pub mint_timestamp: Account<'info, MintTimestamp>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
-// ====================================================================
-// HELPER FUNCTIONS
-// ====================================================================
+#[derive(Accounts)]
+pub struct SetRewardCurve<'info> {
+    /// Reward curve PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RewardCurve::LEN,
+        seeds = [b"reward-curve"],
+        bump
+    )]
+    pub reward_curve: Account<'info, RewardCurve>,
 
-/// Calculate tier based on treasure amount (with 6 decimals)
-/// Returns tier 1-4, higher tier = more tokens hidden
-fn calculate_tier(amount: u64) -> u8 {
-    let tokens = amount / 1_000_000; // Convert from lamports to tokens
+    /// BOOTY state PDA
+    #[account(
+        seeds = [b"booty-state"],
+        bump = booty_state.bump,
+        constraint = booty_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub booty_state: Account<'info, BootyState>,
 
-    if tokens >= 100_000 {
-        4 // Legendary tier
-    } else if tokens >= 10_000 {
-        3 // Epic tier
-    } else if tokens >= 1_000 {
-        2 // Rare tier
-    } else {
-        1 // Common tier
-    }
-}
+    /// BOOTY multisig PDA (when it exists, its threshold of signers must approve)
+    /// CHECK: May not exist yet; deserialized by check_booty_multisig when it does
+    #[account(seeds = [b"booty-multisig"], bump)]
+    pub booty_multisig: UncheckedAccount<'info>,
 
-// ====================================================================
-// ACCOUNT STRUCTURES
-// ====================================================================
+    /// BOOTY authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
-/// Main vault account storing program configuration
-#[account]
-pub struct TreasureVault {
-    pub authority: Pubkey,    // Admin who can update settings (32 bytes)
-    pub total_hidden: u64,    // Total tokens hidden (8 bytes)
-    pub total_claimed: u64,   // Total treasures claimed (8 bytes)
-    pub bump: u8,             // PDA bump (1 byte)
+    pub system_program: Program<'info, System>,
 }
 
-impl TreasureVault {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 1; // discriminator + fields
-}
+#[derive(Accounts)]
+pub struct SetReferralBonus<'info> {
+    /// Referral config PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ReferralConfig::LEN,
+        seeds = [b"referral-config"],
+        bump
+    )]
+    pub referral_config: Account<'info, ReferralConfig>,
 
-/// Player treasure record (one per player per hidden treasure)
-#[account]
-pub struct TreasureRecord {
-    pub player: Pubkey,    // Player's wallet (32 bytes)
-    pub amount: u64,       // Amount hidden (8 bytes)
-    pub timestamp: i64,    // When hidden (8 bytes)
-    pub claimed: bool,     // Has treasure been claimed? (1 byte)
-    pub tier: u8,          // Tier earned (1-4) (1 byte)
-    pub bump: u8,          // PDA bump (1 byte)
-}
+    /// BOOTY state PDA
+    #[account(
+        seeds = [b"booty-state"],
+        bump = booty_state.bump,
+        constraint = booty_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub booty_state: Account<'info, BootyState>,
 
-impl TreasureRecord {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 1 + 1 + 1; // discriminator + fields
-}
+    /// BOOTY multisig PDA (when it exists, its threshold of signers must approve)
+    /// CHECK: May not exist yet; deserialized by check_booty_multisig when it does
+    #[account(seeds = [b"booty-multisig"], bump)]
+    pub booty_multisig: UncheckedAccount<'info>,
 
-/// Player search record (one per player per search attempt)
-#[account]
-pub struct SearchRecord {
-    pub player: Pubkey,    // Player's wallet (32 bytes)
-    pub x: i32,            // X coordinate searched (4 bytes)
-    pub y: i32,            // Y coordinate searched (4 bytes)
-    pub timestamp: i64,    // When searched (8 bytes)
-    pub found: bool,       // Was treasure found? (1 byte)
-    pub bump: u8,          // PDA bump (1 byte)
-}
+    /// BOOTY authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
-impl SearchRecord {
-    pub const LEN: usize = 8 + 32 + 4 + 4 + 8 + 1 + 1; // discriminator + fields
+    pub system_program: Program<'info, System>,
 }
 
-/// Token whitelist entry (which tokens can be hidden as treasure)
-#[account]
-pub struct TokenWhitelist {
-    pub token_mint: Pubkey,   // Token mint address (32 bytes)
-    pub enabled: bool,        // Is this token enabled? (1 byte)
-    pub bump: u8,             // PDA bump (1 byte)
-}
+#[derive(Accounts)]
+pub struct UpdateBootyState<'info> {
+    /// BOOTY state PDA
+    #[account(
+        mut,
+        seeds = [b"booty-state"],
+        bump = booty_state.bump,
+        constraint = booty_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub booty_state: Account<'info, BootyState>,
 
-impl TokenWhitelist {
-    pub const LEN: usize = 8 + 32 + 1 + 1; // discriminator + fields
-}
+    /// BOOTY multisig PDA (when it exists, its threshold of signers must approve)
+    /// CHECK: May not exist yet; deserialized by check_booty_multisig when it does
+    #[account(seeds = [b"booty-multisig"], bump)]
+    pub booty_multisig: UncheckedAccount<'info>,
 
-/// BOOTY token state tracking
-#[account]
-pub struct BootyState {
-    pub mint: Pubkey,              // The BOOTY token mint address (32 bytes)
-    pub authority: Pubkey,          // Authority that manages the token (32 bytes)
-    pub total_mined: u64,          // Total tokens mined/minted (8 bytes)
-    pub total_burned: u64,         // Total tokens burned (8 bytes)
-    pub max_supply: Option<u64>,   // Optional maximum supply (1 + 8 bytes)
-    pub bump: u8,                  // PDA bump (1 byte)
+    /// BOOTY admin authority
+    pub authority: Signer<'info>,
 }
 
-impl BootyState {
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1 + 8 + 1; // discriminator + fields
-}
+#[derive(Accounts)]
+pub struct SetBootyMultisig<'info> {
+    /// BOOTY state PDA
+    #[account(
+        seeds = [b"booty-state"],
+        bump = booty_state.bump,
+        constraint = booty_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub booty_state: Account<'info, BootyState>,
 
-// ====================================================================
-// ACCOUNT CONTEXTS (defines which accounts each instruction needs)
-// ====================================================================
+    /// BOOTY multisig PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = BootyMultisig::LEN,
+        seeds = [b"booty-multisig"],
+        bump
+    )]
+    pub booty_multisig: Account<'info, BootyMultisig>,
 
-#[derive(Accounts)]
-pub struct MintNFT<'info> {
-    /// The player's wallet that will receive the NFT
+    /// BOOTY admin authority
     #[account(mut)]
-    pub player: SystemAccount<'info>,
+    pub authority: Signer<'info>,
 
-    /// The payer/authority (game backend wallet) that pays for and authorizes minting
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
 
-    /// The mint account for this specific NFT
+#[derive(Accounts)]
+pub struct FreezeBootyAccount<'info> {
+    /// BOOTY state PDA (mint and freeze authority)
     #[account(
-        init,
-        payer = payer,
-        mint::decimals = 0,
-        mint::authority = payer,
-        mint::freeze_authority = payer,
+        seeds = [b"booty-state"],
+        bump = booty_state.bump,
+        constraint = booty_state.authority == authority.key() @ ErrorCode::Unauthorized
     )]
-    pub mint: Account<'info, Mint>,
+    pub booty_state: Account<'info, BootyState>,
 
-    /// The token account that will hold the NFT for the player
+    /// BOOTY token mint
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = mint,
-        associated_token::authority = player,
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
     )]
-    pub token_account: Account<'info, TokenAccount>,
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
 
-    /// Metaplex metadata account
-    /// CHECK: This account is created by the Metaplex program
-    #[account(mut)]
-    pub metadata: UncheckedAccount<'info>,
+    /// Player's BOOTY token account being frozen or thawed
+    #[account(
+        mut,
+        constraint = player_booty_account.mint == booty_state.mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub player_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_metadata_program: Program<'info, Metaplex>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+    /// BOOTY multisig PDA (when it exists, its threshold of signers must approve)
+    /// CHECK: May not exist yet; deserialized by check_booty_multisig when it does
+    #[account(seeds = [b"booty-multisig"], bump)]
+    pub booty_multisig: UncheckedAccount<'info>,
+
+    /// BOOTY admin authority
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeVault<'info> {
-    /// Vault PDA that stores program config
+pub struct CreateBootyMetadata<'info> {
+    /// BOOTY state PDA (mint authority, signs the metadata CPI)
     #[account(
-        init,
-        payer = authority,
-        space = TreasureVault::LEN,
-        seeds = [b"vault"],
+        seeds = [b"booty-state"],
+        bump = booty_state.bump,
+        constraint = booty_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    /// BOOTY token mint
+    #[account(
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// Metaplex metadata account for the BOOTY mint
+    /// CHECK: Address is seeds-checked; created by the Metaplex program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            booty_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
         bump
     )]
-    pub vault: Account<'info, TreasureVault>,
+    pub metadata: UncheckedAccount<'info>,
 
-    /// Admin who initializes the program
+    /// BOOTY admin authority (pays for the metadata account)
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    pub token_metadata_program: Program<'info, Metaplex>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-#[instruction(amount: u64, treasure_id: i64)]
-pub struct HideTreasure<'info> {
-    /// Player hiding the treasure
+pub struct StakeBooty<'info> {
+    /// Player staking BOOTY
     #[account(mut)]
     pub player: Signer<'info>,
 
-    /// Player's token account (source of tokens)
+    /// BOOTY token mint
     #[account(
-        mut,
-        constraint = player_token_account.owner == player.key() @ ErrorCode::InvalidTokenAccount
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
     )]
-    pub player_token_account: Account<'info, TokenAccount>,
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
 
-    /// Vault's token account (destination)
-    #[account(mut)]
-    pub vault_token_account: Account<'info, TokenAccount>,
-
-    /// Vault PDA
+    /// Player's BOOTY token account (source)
     #[account(
         mut,
-        seeds = [b"vault"],
-        bump = vault.bump
+        constraint = player_booty_account.owner == player.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = player_booty_account.mint == booty_mint.key() @ ErrorCode::InvalidTokenAccount
     )]
-    pub vault: Account<'info, TreasureVault>,
+    pub player_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
 
-    /// Treasure record PDA (unique per player, per treasure)
-    /// Using treasure_id as seed to allow multiple treasures per player
+    /// Stake vault holding all staked BOOTY (owned by the BOOTY state PDA)
+    #[account(
+        init_if_needed,
+        payer = player,
+        seeds = [b"stake-vault"],
+        bump,
+        token::mint = booty_mint,
+        token::authority = booty_state,
+        token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// Player's stake record PDA
     #[account(
         init,
         payer = player,
-        space = TreasureRecord::LEN,
-        seeds = [
-            b"treasure",
-            player.key().as_ref(),
-            &treasure_id.to_le_bytes()
-        ],
+        space = StakeRecord::LEN,
+        seeds = [b"stake", player.key().as_ref()],
         bump
     )]
-    pub treasure_record: Account<'info, TreasureRecord>,
+    pub stake_record: Account<'info, StakeRecord>,
 
-    pub token_program: Program<'info, Token>,
+    /// BOOTY state PDA
+    #[account(
+        seeds = [b"booty-state"],
+        bump = booty_state.bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimTreasure<'info> {
-    /// Player claiming the treasure
+pub struct UnstakeBooty<'info> {
+    /// Player unstaking BOOTY (receives stake record rent back)
+    #[account(mut)]
     pub player: Signer<'info>,
 
-    /// Treasure record being claimed
+    /// BOOTY token mint
     #[account(
         mut,
-        constraint = treasure_record.player == player.key() @ ErrorCode::Unauthorized,
-        constraint = !treasure_record.claimed @ ErrorCode::AlreadyClaimed
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
     )]
-    pub treasure_record: Account<'info, TreasureRecord>,
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
 
-    /// Vault PDA
+    /// Player's BOOTY token account (receives principal and rewards)
     #[account(
         mut,
-        seeds = [b"vault"],
-        bump = vault.bump
+        constraint = player_booty_account.owner == player.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = player_booty_account.mint == booty_mint.key() @ ErrorCode::InvalidTokenAccount
     )]
-    pub vault: Account<'info, TreasureVault>,
-}
-
-#[derive(Accounts)]
-#[instruction(x: i32, y: i32, search_id: i64)]
-pub struct SearchTreasure<'info> {
-    /// Player searching for treasure
-    #[account(mut)]
-    pub player: Signer<'info>,
+    pub player_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
 
-    /// Player's BOOTY token account (source of search fee payment)
+    /// Stake vault holding all staked BOOTY
     #[account(
         mut,
-        constraint = player_booty_account.owner == player.key() @ ErrorCode::InvalidTokenAccount
+        seeds = [b"stake-vault"],
+        bump
     )]
-    pub player_booty_account: Account<'info, TokenAccount>,
-
-    /// Vault's BOOTY token account (destination for search fees)
-    #[account(mut)]
-    pub vault_booty_account: Account<'info, TokenAccount>,
+    pub stake_vault: InterfaceAccount<'info, InterfaceTokenAccount>,
 
-    /// Search record PDA (unique per player, per search)
+    /// Player's stake record PDA (closed on unstake)
     #[account(
-        init,
-        payer = player,
-        space = SearchRecord::LEN,
-        seeds = [
-            b"search",
-            player.key().as_ref(),
-            &search_id.to_le_bytes()
-        ],
-        bump
+        mut,
+        seeds = [b"stake", player.key().as_ref()],
+        bump = stake_record.bump,
+        constraint = stake_record.player == player.key() @ ErrorCode::Unauthorized,
+        close = player
+    )]
+    pub stake_record: Account<'info, StakeRecord>,
+
+    /// BOOTY state PDA (mint authority for rewards, owner of the stake vault)
+    #[account(
+        mut,
+        seeds = [b"booty-state"],
+        bump = booty_state.bump
     )]
-    pub search_record: Account<'info, SearchRecord>,
+    pub booty_state: Account<'info, BootyState>,
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct WhitelistToken<'info> {
-    /// Whitelist PDA for this token
+pub struct CreateVesting<'info> {
+    /// Vesting schedule PDA for the beneficiary
     #[account(
         init,
         payer = authority,
-        space = TokenWhitelist::LEN,
-        seeds = [b"whitelist", token_mint.key().as_ref()],
+        space = VestingSchedule::LEN,
+        seeds = [b"vesting", beneficiary.key().as_ref()],
         bump
     )]
-    pub whitelist: Account<'info, TokenWhitelist>,
+    pub vesting_schedule: Account<'info, VestingSchedule>,
 
-    /// Token mint being whitelisted
-    /// CHECK: We're just storing the pubkey, not reading data
-    pub token_mint: UncheckedAccount<'info>,
+    /// Wallet the BOOTY vests to
+    pub beneficiary: SystemAccount<'info>,
 
-    /// Vault PDA
+    /// BOOTY state PDA
     #[account(
-        seeds = [b"vault"],
-        bump = vault.bump,
-        constraint = vault.authority == authority.key() @ ErrorCode::Unauthorized
+        seeds = [b"booty-state"],
+        bump = booty_state.bump,
+        constraint = booty_state.authority == authority.key() @ ErrorCode::Unauthorized
     )]
-    pub vault: Account<'info, TreasureVault>,
+    pub booty_state: Account<'info, BootyState>,
 
-    /// Admin authority
+    /// BOOTY multisig PDA (when it exists, its threshold of signers must approve)
+    /// CHECK: May not exist yet; deserialized by check_booty_multisig when it does
+    #[account(seeds = [b"booty-multisig"], bump)]
+    pub booty_multisig: UncheckedAccount<'info>,
+
+    /// BOOTY authority
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -663,116 +7995,385 @@ pub struct WhitelistToken<'info> {
 }
 
 #[derive(Accounts)]
-pub struct UpdateVault<'info> {
-    /// Vault PDA
+pub struct ClaimVested<'info> {
+    /// Beneficiary claiming vested BOOTY
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// Beneficiary's vesting schedule
     #[account(
         mut,
-        seeds = [b"vault"],
-        bump = vault.bump,
-        constraint = vault.authority == authority.key() @ ErrorCode::Unauthorized
+        seeds = [b"vesting", beneficiary.key().as_ref()],
+        bump = vesting_schedule.bump
     )]
-    pub vault: Account<'info, TreasureVault>,
+    pub vesting_schedule: Account<'info, VestingSchedule>,
 
-    /// Current admin authority
-    pub authority: Signer<'info>,
+    /// BOOTY token mint
+    #[account(
+        mut,
+        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+    )]
+    pub booty_mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// Beneficiary's BOOTY token account (auto-created if needed)
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = booty_mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
+    )]
+    pub beneficiary_booty_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// BOOTY state PDA (mint authority)
+    #[account(
+        mut,
+        seeds = [b"booty-state"],
+        bump = booty_state.bump
+    )]
+    pub booty_state: Account<'info, BootyState>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(decimals: u8)]
-pub struct InitializeBootyMint<'info> {
-    /// The BOOTY token mint (standard SPL token)
+#[instruction(trade_id: u64)]
+pub struct CreateTradeOffer<'info> {
+    /// Player making the offer
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    /// Mint of the asset being offered
+    pub offer_mint: Account<'info, Mint>,
+
+    /// Maker's token account holding the offered asset
     #[account(
-        init,
-        payer = authority,
-        mint::decimals = decimals,
-        mint::authority = booty_state,
+        mut,
+        constraint = maker_offer_account.owner == maker.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = maker_offer_account.mint == offer_mint.key() @ ErrorCode::InvalidTokenAccount
     )]
-    pub booty_mint: Account<'info, Mint>,
+    pub maker_offer_account: Account<'info, TokenAccount>,
 
-    /// BOOTY state PDA that tracks supply
+    /// Trade offer PDA
     #[account(
         init,
-        payer = authority,
-        space = BootyState::LEN,
-        seeds = [b"booty-state"],
+        payer = maker,
+        space = TradeOffer::LEN,
+        seeds = [b"trade", maker.key().as_ref(), &trade_id.to_le_bytes()],
         bump
     )]
-    pub booty_state: Account<'info, BootyState>,
+    pub trade_offer: Account<'info, TradeOffer>,
 
-    /// Admin who initializes the BOOTY token
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    /// Escrow token account holding the offered asset (owned by the offer PDA)
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"trade-escrow", trade_offer.key().as_ref()],
+        bump,
+        token::mint = offer_mint,
+        token::authority = trade_offer,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct MineBooty<'info> {
-    /// Player receiving BOOTY tokens
-    #[account(mut)]
-    pub player: Signer<'info>,
+pub struct AcceptTrade<'info> {
+    /// Player accepting the offer
+    pub taker: Signer<'info>,
 
-    /// BOOTY token mint
+    /// Maker of the offer (receives the offer and escrow rent back)
+    /// CHECK: Must match trade_offer.maker
     #[account(
         mut,
-        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+        constraint = maker.key() == trade_offer.maker @ ErrorCode::Unauthorized
     )]
-    pub booty_mint: Account<'info, Mint>,
+    pub maker: UncheckedAccount<'info>,
 
-    /// Player's BOOTY token account (auto-created if needed)
+    /// Trade offer PDA (closed once the trade settles)
     #[account(
-        init_if_needed,
-        payer = player,
-        associated_token::mint = booty_mint,
-        associated_token::authority = player,
+        mut,
+        seeds = [b"trade", trade_offer.maker.as_ref(), &trade_offer.trade_id.to_le_bytes()],
+        bump = trade_offer.bump,
+        close = maker
     )]
-    pub player_booty_account: Account<'info, TokenAccount>,
+    pub trade_offer: Account<'info, TradeOffer>,
 
-    /// BOOTY state PDA
+    /// Escrow token account holding the offered asset
     #[account(
         mut,
-        seeds = [b"booty-state"],
-        bump = booty_state.bump
+        seeds = [b"trade-escrow", trade_offer.key().as_ref()],
+        bump
     )]
-    pub booty_state: Account<'info, BootyState>,
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// Taker's token account paying the requested asset
+    #[account(
+        mut,
+        constraint = taker_request_account.owner == taker.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = taker_request_account.mint == trade_offer.request_mint @ ErrorCode::TradeMismatch
+    )]
+    pub taker_request_account: Account<'info, TokenAccount>,
+
+    /// Maker's token account receiving the requested asset
+    #[account(
+        mut,
+        constraint = maker_request_account.owner == trade_offer.maker @ ErrorCode::InvalidTokenAccount,
+        constraint = maker_request_account.mint == trade_offer.request_mint @ ErrorCode::TradeMismatch
+    )]
+    pub maker_request_account: Account<'info, TokenAccount>,
+
+    /// Taker's token account receiving the offered asset
+    #[account(
+        mut,
+        constraint = taker_offer_account.owner == taker.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = taker_offer_account.mint == trade_offer.offer_mint @ ErrorCode::TradeMismatch
+    )]
+    pub taker_offer_account: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct BurnBootyForTravel<'info> {
-    /// Player burning BOOTY tokens
-    pub player: Signer<'info>,
+pub struct CancelTrade<'info> {
+    /// Maker cancelling the offer
+    #[account(mut)]
+    pub maker: Signer<'info>,
 
-    /// BOOTY token mint
+    /// Trade offer PDA (closed, rent returned to the maker)
     #[account(
         mut,
-        constraint = booty_mint.key() == booty_state.mint @ ErrorCode::InvalidBootyMint
+        seeds = [b"trade", maker.key().as_ref(), &trade_offer.trade_id.to_le_bytes()],
+        bump = trade_offer.bump,
+        constraint = trade_offer.maker == maker.key() @ ErrorCode::Unauthorized,
+        close = maker
     )]
-    pub booty_mint: Account<'info, Mint>,
+    pub trade_offer: Account<'info, TradeOffer>,
 
-    /// Player's BOOTY token account
+    /// Escrow token account holding the offered asset
     #[account(
         mut,
-        constraint = player_booty_account.owner == player.key() @ ErrorCode::InvalidTokenAccount
+        seeds = [b"trade-escrow", trade_offer.key().as_ref()],
+        bump
     )]
-    pub player_booty_account: Account<'info, TokenAccount>,
+    pub escrow_token_account: Account<'info, TokenAccount>,
 
-    /// BOOTY state PDA
+    /// Maker's token account receiving the asset back
     #[account(
         mut,
-        seeds = [b"booty-state"],
-        bump = booty_state.bump
+        constraint = maker_offer_account.owner == maker.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = maker_offer_account.mint == trade_offer.offer_mint @ ErrorCode::InvalidTokenAccount
     )]
-    pub booty_state: Account<'info, BootyState>,
+    pub maker_offer_account: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
 }
 
+// ====================================================================
+// EVENTS
+// ====================================================================
+
+/// Emitted when a protocol fee is taken from a treasure deposit
+#[event]
+pub struct ProtocolFeeCollected {
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub fee: u64,
+    pub net_amount: u64,
+}
+
+/// Emitted when a player claims a hidden treasure
+#[event]
+pub struct TreasureClaimed {
+    pub player: Pubkey,
+    pub tier: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when a hide moves a player onto (or up) the leaderboard
+#[event]
+pub struct LeaderboardChanged {
+    pub player: Pubkey,
+    pub total_hidden: u64,
+    pub rank: u8,
+}
+
+/// Emitted for every NFT minted, once its metadata exists
+#[event]
+pub struct CollectibleMinted {
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub metadata: Pubkey,
+    pub uri: String,
+    pub tier: Option<u8>, // Monster tier when minted by the game flow
+}
+
+/// Emitted when the authority withdraws accrued protocol fees
+#[event]
+pub struct FeesWithdrawn {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub total_fees_withdrawn: u64,
+}
+
+/// Emitted when an expired treasure is swept to the treasury
+#[event]
+pub struct TreasureForfeited {
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when unattributed vault dust is swept to the treasury
+#[event]
+pub struct DustSwept {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a player's BOOTY account is frozen
+#[event]
+pub struct AccountFrozen {
+    pub account: Pubkey,
+    pub owner: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Emitted when a player's BOOTY account is thawed
+#[event]
+pub struct AccountThawed {
+    pub account: Pubkey,
+    pub owner: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Emitted when the authority confirms a search found treasure
+#[event]
+pub struct FindVerified {
+    pub authority: Pubkey,
+    pub player: Pubkey,
+    pub treasure: Pubkey,
+    pub search_id: i64,
+    pub x: i32,
+    pub y: i32,
+    pub reward: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a soulbound deposit receipt is minted
+#[event]
+pub struct DepositReceiptMinted {
+    pub player: Pubkey,
+    pub treasure: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub created_ts: i64,
+}
+
+/// Emitted when a consumable collectible is used
+#[event]
+pub struct CollectibleUsed {
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub remaining: u64, // Uses left after this one (0 means the NFT was burned)
+}
+
+/// Emitted when two monsters are combined into a higher tier
+#[event]
+pub struct UpgradeCompleted {
+    pub burned: [Pubkey; 2],
+    pub minted: Pubkey,
+}
+
+/// Emitted for each item minted into a collection
+#[event]
+pub struct CollectionMinted {
+    pub collection_mint: Pubkey,
+    pub minted: u64,
+    pub max_supply: u64,
+}
+
+/// Emitted when the admin claims a treasure on a player's behalf
+#[event]
+pub struct TreasureForceClaimed {
+    pub treasure_record: Pubkey,
+    pub player: Pubkey,
+    pub destination: Pubkey,
+    pub authority: Pubkey,
+    pub reason_code: u8,
+}
+
+/// Emitted whenever a vault's hidden or claimed totals change
+#[event]
+pub struct VaultStatsChanged {
+    pub vault_id: u64,
+    pub total_hidden: u64,
+    pub total_claimed: u64,
+}
+
+/// Emitted with the final tallies when a season ends
+#[event]
+pub struct SeasonEnded {
+    pub season: u32,
+    pub total_hidden: u64,
+    pub total_claimed: u64,
+}
+
+/// Emitted when a player reclaims unclaimed treasure
+#[event]
+pub struct TreasureReclaimed {
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a vault token account is handed to a new owner
+#[event]
+pub struct VaultTokenAuthorityChanged {
+    pub token_account: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+/// Emitted when an emergency drain is proposed
+#[event]
+pub struct DrainProposed {
+    pub vault_token_account: Pubkey,
+    pub destination: Pubkey,
+    pub unlock_ts: i64,
+}
+
+/// Emitted when an emergency drain executes
+#[event]
+pub struct VaultDrained {
+    pub vault_token_account: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when the admin withdraws tokens from the vault
+#[event]
+pub struct VaultWithdrawal {
+    pub authority: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub total_hidden: u64,
+}
+
+/// Emitted when the admin burns deposited treasure tokens
+#[event]
+pub struct TreasureBurned {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_burned_treasure: u64,
+}
+
 // ====================================================================
 // ERROR CODES
 // ====================================================================
@@ -799,4 +8400,277 @@ pub enum ErrorCode {
 
     #[msg("Invalid BOOTY mint")]
     InvalidBootyMint,
+
+    #[msg("Creator shares must total 100 (maximum 5 creators)")]
+    InvalidCreatorShares,
+
+    #[msg("NFT metadata is immutable")]
+    ImmutableMetadata,
+
+    #[msg("Number of accounts does not match the number of items")]
+    AccountCountMismatch,
+
+    #[msg("Batch is too large")]
+    BatchTooLarge,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Insufficient vault balance")]
+    InsufficientVaultBalance,
+
+    #[msg("Token is not whitelisted")]
+    TokenNotWhitelisted,
+
+    #[msg("Invalid price feed")]
+    InvalidPriceFeed,
+
+    #[msg("Price feed is stale")]
+    StalePriceFeed,
+
+    #[msg("Fee is too high (maximum 10%)")]
+    FeeTooHigh,
+
+    #[msg("Stake amount must be greater than zero")]
+    InvalidStakeAmount,
+
+    #[msg("Invalid staking parameters")]
+    InvalidStakingParams,
+
+    #[msg("Stake lockup period has not elapsed")]
+    StakeNotMatured,
+
+    #[msg("Mining rate limit exceeded for the current window")]
+    MiningRateExceeded,
+
+    #[msg("Rate limit window must be positive when a limit is set")]
+    InvalidRateLimit,
+
+    #[msg("Max supply can only be increased")]
+    CannotDecreaseMaxSupply,
+
+    #[msg("Max supply cannot be below the amount already mined")]
+    MaxBelowMinted,
+
+    #[msg("Vault still holds treasure")]
+    VaultNotEmpty,
+
+    #[msg("Payer is not an authorized minter")]
+    UnauthorizedMinter,
+
+    #[msg("Minter is already registered")]
+    MinterAlreadyRegistered,
+
+    #[msg("Minter registry is full")]
+    TooManyMinters,
+
+    #[msg("Merkle proof does not match the allowlist")]
+    InvalidMerkleProof,
+
+    #[msg("Allowlist spot already minted")]
+    AllowlistAlreadyClaimed,
+
+    #[msg("Account is already at the current version")]
+    AlreadyMigrated,
+
+    #[msg("Account cannot be migrated")]
+    InvalidMigrationTarget,
+
+    #[msg("Note exceeds the maximum length")]
+    NoteTooLong,
+
+    #[msg("Upgrade inputs must be two distinct monsters of the same family and tier")]
+    MismatchedInputs,
+
+    #[msg("Monster is already at the highest tier")]
+    MaxMonsterTier,
+
+    #[msg("Trade amounts must be greater than zero")]
+    InvalidTradeAmount,
+
+    #[msg("Token account does not match the asset recorded in the trade")]
+    TradeMismatch,
+
+    #[msg("Player already has a different referrer")]
+    ReferrerAlreadySet,
+
+    #[msg("Players cannot refer themselves")]
+    SelfReferral,
+
+    #[msg("Referral bonus exceeds the maximum")]
+    ReferralBonusTooHigh,
+
+    #[msg("Vesting schedule is invalid")]
+    InvalidVestingSchedule,
+
+    #[msg("Vesting cliff has not been reached")]
+    CliffNotReached,
+
+    #[msg("No newly vested BOOTY to claim")]
+    NothingVested,
+
+    #[msg("Metadata name exceeds 32 bytes")]
+    NameTooLong,
+
+    #[msg("Metadata symbol exceeds 10 bytes")]
+    SymbolTooLong,
+
+    #[msg("Metadata URI exceeds 200 bytes")]
+    UriTooLong,
+
+    #[msg("Metadata URI cannot be empty")]
+    EmptyUri,
+
+    #[msg("Collectible has no uses left to consume")]
+    NotConsumable,
+
+    #[msg("Tier multiplier must be between 1 and 20,000 bps")]
+    InvalidTierMultiplier,
+
+    #[msg("Search record address does not match its seeds")]
+    InvalidSearchRecord,
+
+    #[msg("Search has already been verified as found")]
+    AlreadyFound,
+
+    #[msg("Mint does not match the whitelisted token")]
+    MintMismatch,
+
+    #[msg("Deposit would exceed the player's lifetime deposit cap")]
+    PlayerDepositCapExceeded,
+
+    #[msg("Claim window has passed for this treasure")]
+    ClaimExpired,
+
+    #[msg("Treasure is still within its claim window")]
+    ClaimNotExpired,
+
+    #[msg("Claim window cannot be negative")]
+    InvalidClaimWindow,
+
+    #[msg("Multisig needs 1 to 10 distinct authorities and a threshold no larger than their count")]
+    InvalidMultisig,
+
+    #[msg("Not enough multisig authorities signed")]
+    ThresholdNotMet,
+
+    #[msg("BOOTY has already been mined for this treasure")]
+    AlreadyRewarded,
+
+    #[msg("Reward curve gives no reward for this tier")]
+    InvalidRewardCurve,
+
+    #[msg("Treasure tier is below the expected tier")]
+    TierBelowExpected,
+
+    #[msg("Whitelist entry address does not match its seeds")]
+    InvalidWhitelistEntry,
+
+    #[msg("Metadata symbol is not allowed by the NFT policy")]
+    SymbolNotAllowed,
+
+    #[msg("Metadata title must start with the NFT policy's name prefix")]
+    NamePrefixRequired,
+
+    #[msg("Withdrawal exceeds the fees collected")]
+    InsufficientFees,
+
+    #[msg("Treasure cannot be claimed yet")]
+    ClaimTooEarly,
+
+    #[msg("Claim delay cannot be negative")]
+    InvalidClaimDelay,
+
+    #[msg("Claim amount must be non-zero and within the remaining balance")]
+    ClaimExceedsRemaining,
+
+    #[msg("BOOTY burned exceeds BOOTY mined")]
+    SupplyAccountingError,
+
+    #[msg("Whitelist index is full")]
+    WhitelistFull,
+
+    #[msg("Daily mining cap exceeded for this player")]
+    DailyCapExceeded,
+
+    #[msg("Freeze authority must be the minting payer")]
+    InvalidFreezeAuthority,
+
+    #[msg("BOOTY has not been held long enough to burn")]
+    HoldTimeNotMet,
+
+    #[msg("Hold time cannot be negative")]
+    InvalidHoldTime,
+
+    #[msg("Vault token account holds no unattributed dust")]
+    NoDust,
+
+    #[msg("Treasure record belongs to a different vault")]
+    VaultMismatch,
+
+    #[msg("Treasure record account must be writable")]
+    InvalidTreasureRecord,
+
+    #[msg("Decimals must be at most 9")]
+    InvalidDecimals,
+
+    #[msg("Bonus window must start before it ends, with a 1x-3x multiplier")]
+    InvalidBonusWindow,
+
+    #[msg("Refunds must go back to the token account the treasure was hidden from")]
+    SourceAccountMismatch,
+
+    #[msg("Treasure's BOOTY reward must be mined or waived before claiming")]
+    RewardPending,
+
+    #[msg("Program must be paused for this operation")]
+    ProgramNotPaused,
+
+    #[msg("This treasure_id has already been used")]
+    TreasureIdReused,
+
+    #[msg("Collection has reached its max supply")]
+    CollectionSoldOut,
+
+    #[msg("Royalty cannot exceed 10000 basis points")]
+    InvalidRoyaltyBps,
+
+    #[msg("Player does not hold the required collectible")]
+    CollectibleNotHeld,
+
+    #[msg("Drain timelock has not elapsed yet")]
+    DrainTimelockActive,
+
+    #[msg("Whitelist entry must be disabled before it is closed")]
+    MustDisableFirst,
+
+    #[msg("Id must be greater than the player's last id")]
+    NonMonotonicId,
+
+    #[msg("Treasure has been partly reclaimed by its player and can't be claimed")]
+    AlreadyReclaimed,
+
+    #[msg("No NFTs of this tier are left to mint")]
+    TierSoldOut,
+
+    #[msg("Dust can only be swept while a single mint is whitelisted")]
+    MultiMintVault,
+
+    #[msg("Creators must include the royalty recipient")]
+    RoyaltyRecipientMissing,
+
+    #[msg("BOOTY reward for this treasure was waived")]
+    RewardWaived,
+
+    #[msg("Treasure must be claimed before its NFT is minted")]
+    TreasureNotClaimed,
+
+    #[msg("An NFT has already been minted for this treasure")]
+    NftAlreadyMinted,
+
+    #[msg("Tiered mints need the tier supply account")]
+    TierSupplyRequired,
 }